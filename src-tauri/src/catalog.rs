@@ -0,0 +1,524 @@
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use crate::database::{CatalogItem, DEFAULT_ACTIVITY_TYPES, DEFAULT_PROJECTS, DEFAULT_TAGS};
+use crate::error::AppError;
+use crate::markdown::ParsedJournalEntry;
+use crate::profile::Profile;
+
+// Prochain identifiant à attribuer, enregistré à côté des éléments pour ne
+// jamais redonner celui d'un élément supprimé
+pub const NEXT_ID_KEY: &str = "next_id";
+
+// Anciens noms des éléments renommés ou fusionnés, pour que les rapports les
+// regroupent sous le nom actuel
+pub const ALIASES_KEY: &str = "aliases";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogKind {
+    Projects,
+    Tags,
+    ActivityTypes,
+}
+
+impl CatalogKind {
+    // Clé des éléments dans le store
+    pub fn key(self) -> &'static str {
+        match self {
+            CatalogKind::Projects => "projects",
+            CatalogKind::Tags => "tags",
+            CatalogKind::ActivityTypes => "activity_types",
+        }
+    }
+
+    // Projets et tags appartiennent au profil ; les types d'activité sont
+    // communs à tous les profils
+    pub fn store_name(self, profile: &Profile) -> String {
+        match self {
+            CatalogKind::ActivityTypes => "activity-types.json".to_string(),
+            _ => profile.catalog_store(self.key()),
+        }
+    }
+
+    fn default_color(self) -> &'static str {
+        match self {
+            CatalogKind::Projects => "#007bff",
+            CatalogKind::Tags | CatalogKind::ActivityTypes => "#6c757d",
+        }
+    }
+
+    fn not_found(self) -> &'static str {
+        match self {
+            CatalogKind::Projects => "Projet non trouvé",
+            CatalogKind::Tags => "Tag non trouvé",
+            CatalogKind::ActivityTypes => "Type d'activité non trouvé",
+        }
+    }
+
+    fn duplicate(self) -> &'static str {
+        match self {
+            CatalogKind::Projects => "Un projet avec ce nom existe déjà",
+            CatalogKind::Tags => "Un tag avec ce nom existe déjà",
+            CatalogKind::ActivityTypes => "Un type d'activité avec ce nom existe déjà",
+        }
+    }
+
+    pub fn defaults(self) -> Vec<CatalogItem> {
+        let defaults = match self {
+            CatalogKind::Projects => DEFAULT_PROJECTS,
+            CatalogKind::Tags => DEFAULT_TAGS,
+            CatalogKind::ActivityTypes => DEFAULT_ACTIVITY_TYPES,
+        };
+        defaults
+            .iter()
+            .zip(1..)
+            .map(|((name, description, color), id)| CatalogItem {
+                id: Some(id),
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                color: color.to_string(),
+                active: true,
+                created_at: None,
+                updated_at: None,
+            })
+            .collect()
+    }
+}
+
+// Les noms se comparent sans tenir compte de la casse ni des espaces autour
+fn name_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    name_key(a) == name_key(b)
+}
+
+// Remplace `from` par `to` dans l'entrée ; renvoie `true` si elle a changé.
+// Un tag déjà présent sous le nouveau nom n'est pas dupliqué.
+pub fn rename_in_entry(
+    kind: CatalogKind,
+    entry: &mut ParsedJournalEntry,
+    from: &str,
+    to: &str,
+) -> bool {
+    let field = match kind {
+        CatalogKind::Projects => &mut entry.project,
+        CatalogKind::ActivityTypes => &mut entry.entry_type,
+        CatalogKind::Tags => {
+            if !entry.tags.iter().any(|tag| same_name(tag, from)) {
+                return false;
+            }
+            let mut tags: Vec<String> = Vec::with_capacity(entry.tags.len());
+            for tag in entry.tags.drain(..) {
+                let tag = if same_name(&tag, from) {
+                    to.to_string()
+                } else {
+                    tag
+                };
+                if !tags.iter().any(|existing| same_name(existing, &tag)) {
+                    tags.push(tag);
+                }
+            }
+            entry.tags = tags;
+            return true;
+        }
+    };
+    if !same_name(field, from) || field == to {
+        return false;
+    }
+    *field = to.to_string();
+    true
+}
+
+fn color_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^#[0-9a-fA-F]{6}$").unwrap())
+}
+
+// Catalogue chargé depuis son store. Les modifications sont validées ici ;
+// l'appelant enregistre le catalogue si `is_changed`.
+#[derive(Debug)]
+pub struct Catalog {
+    kind: CatalogKind,
+    items: Vec<CatalogItem>,
+    next_id: i64,
+    // Ancien nom en minuscules -> nom actuel
+    aliases: BTreeMap<String, String>,
+    changed: bool,
+}
+
+impl Catalog {
+    // Un catalogue absent est créé avec les valeurs par défaut. Illisible, il
+    // est remplacé par celles-ci pour l'affichage mais n'est pas écrasé tant
+    // qu'il n'est pas modifié.
+    pub fn from_store(
+        kind: CatalogKind,
+        items: Option<Value>,
+        next_id: Option<Value>,
+        aliases: Option<Value>,
+    ) -> Self {
+        let (items, changed) = match items.map(serde_json::from_value::<Vec<CatalogItem>>) {
+            Some(Ok(items)) => (items, false),
+            Some(Err(e)) => {
+                tracing::warn!("{} catalog unreadable, using defaults: {}", kind.key(), e);
+                (kind.defaults(), false)
+            }
+            None => (kind.defaults(), true),
+        };
+        let after_items = items.iter().filter_map(|item| item.id).max().unwrap_or(0) + 1;
+        let next_id = next_id
+            .and_then(|value| value.as_i64())
+            .unwrap_or(0)
+            .max(after_items);
+        let aliases = aliases
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        Self {
+            kind,
+            items,
+            next_id,
+            aliases,
+            changed,
+        }
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.changed
+    }
+
+    pub fn all(&self) -> &[CatalogItem] {
+        &self.items
+    }
+
+    pub fn next_id(&self) -> i64 {
+        self.next_id
+    }
+
+    pub fn aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
+    }
+
+    pub fn kind(&self) -> CatalogKind {
+        self.kind
+    }
+
+    // Les alias qui menaient à `from` mènent désormais à `to` ; un nom qui
+    // redevient actuel n'est plus un alias
+    fn add_alias(&mut self, from: &str, to: &str) {
+        self.aliases.remove(&name_key(to));
+        for target in self.aliases.values_mut() {
+            if same_name(target, from) {
+                *target = to.to_string();
+            }
+        }
+        if !same_name(from, to) {
+            self.aliases.insert(name_key(from), to.to_string());
+        }
+    }
+
+    pub fn list(&self, include_inactive: bool) -> Vec<CatalogItem> {
+        self.items
+            .iter()
+            .filter(|item| include_inactive || item.active)
+            .cloned()
+            .collect()
+    }
+
+    // Nom obligatoire et unique sans tenir compte de la casse, couleur en
+    // hexadécimal
+    fn validate(
+        &self,
+        id: Option<i64>,
+        name: &str,
+        color: Option<&str>,
+    ) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::invalid_field("name", "Le nom est obligatoire"));
+        }
+        // Les tags sont écrits `#tag` et séparés par des espaces dans le journal
+        if self.kind == CatalogKind::Tags && name.contains(|c: char| c.is_whitespace() || c == '#')
+        {
+            return Err(AppError::invalid_field(
+                "name",
+                "Un tag ne peut contenir ni espace ni #",
+            ));
+        }
+        if self
+            .items
+            .iter()
+            .any(|item| item.id != id && same_name(&item.name, name))
+        {
+            return Err(AppError::conflict(self.kind.duplicate())
+                .with_field("name", "Ce nom est déjà utilisé"));
+        }
+        if let Some(color) = color {
+            if !color_regex().is_match(color) {
+                return Err(AppError::invalid_field(
+                    "color",
+                    format!("Couleur invalide « {} » : format #RRGGBB attendu", color),
+                ));
+            }
+        }
+        Ok(name.to_string())
+    }
+
+    fn find_mut(&mut self, id: i64) -> Result<&mut CatalogItem, AppError> {
+        let not_found = self.kind.not_found();
+        self.items
+            .iter_mut()
+            .find(|item| item.id == Some(id))
+            .ok_or_else(|| AppError::not_found(not_found))
+    }
+
+    pub fn create(
+        &mut self,
+        name: &str,
+        description: Option<String>,
+        color: Option<String>,
+        now: i64,
+    ) -> Result<CatalogItem, AppError> {
+        let name = self.validate(None, name, color.as_deref())?;
+        let item = CatalogItem {
+            id: Some(self.next_id),
+            name,
+            description,
+            color: color.unwrap_or_else(|| self.kind.default_color().to_string()),
+            active: true,
+            created_at: Some(now.to_string()),
+            updated_at: None,
+        };
+        self.next_id += 1;
+        // Le nom désigne désormais ce nouvel élément et plus celui qui le portait
+        self.aliases.remove(&name_key(&item.name));
+        self.items.push(item.clone());
+        self.changed = true;
+        Ok(item)
+    }
+
+    // Renvoie l'ancien et le nouveau nom si l'élément a été renommé
+    pub fn update(
+        &mut self,
+        id: i64,
+        name: &str,
+        description: Option<String>,
+        color: Option<String>,
+        now: i64,
+    ) -> Result<Option<(String, String)>, AppError> {
+        let name = self.validate(Some(id), name, color.as_deref())?;
+        let renamed = if self.find_mut(id)?.name != name {
+            Some(self.rename(id, &name, now)?)
+        } else {
+            None
+        };
+        let item = self.find_mut(id)?;
+        item.description = description;
+        if let Some(color) = color {
+            item.color = color;
+        }
+        item.updated_at = Some(now.to_string());
+        self.changed = true;
+        Ok(renamed)
+    }
+
+    // Renomme l'élément et garde l'ancien nom comme alias ; renvoie l'ancien
+    // et le nouveau nom
+    pub fn rename(&mut self, id: i64, name: &str, now: i64) -> Result<(String, String), AppError> {
+        let name = self.validate(Some(id), name, None)?;
+        let item = self.find_mut(id)?;
+        let previous = std::mem::replace(&mut item.name, name.clone());
+        item.updated_at = Some(now.to_string());
+        self.add_alias(&previous, &name);
+        self.changed = true;
+        Ok((previous, name))
+    }
+
+    // Supprime `source_id` au profit de `target_id` ; renvoie le nom retiré
+    // et celui qui le remplace
+    pub fn merge(
+        &mut self,
+        source_id: i64,
+        target_id: i64,
+        now: i64,
+    ) -> Result<(String, String), AppError> {
+        if source_id == target_id {
+            return Err(AppError::invalid_field(
+                "target_id",
+                "Un élément ne peut pas être fusionné avec lui-même",
+            ));
+        }
+        let source = self.find_mut(source_id)?.name.clone();
+        let target = self.find_mut(target_id)?;
+        target.updated_at = Some(now.to_string());
+        let target = target.name.clone();
+        self.items.retain(|item| item.id != Some(source_id));
+        self.add_alias(&source, &target);
+        self.changed = true;
+        Ok((source, target))
+    }
+
+    pub fn toggle(&mut self, id: i64, now: i64) -> Result<(), AppError> {
+        let item = self.find_mut(id)?;
+        item.active = !item.active;
+        item.updated_at = Some(now.to_string());
+        self.changed = true;
+        Ok(())
+    }
+
+    pub fn delete(&mut self, id: i64) -> Result<(), AppError> {
+        let initial_len = self.items.len();
+        self.items.retain(|item| item.id != Some(id));
+        if self.items.len() == initial_len {
+            return Err(AppError::not_found(self.kind.not_found()));
+        }
+        self.changed = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rename_in_entry, Catalog, CatalogKind};
+    use crate::error::ErrorCode;
+    use crate::markdown::ParsedJournalEntry;
+    use serde_json::json;
+
+    #[test]
+    fn seeds_defaults_and_never_reuses_deleted_ids() {
+        let mut catalog = Catalog::from_store(CatalogKind::Tags, None, None, None);
+        assert!(catalog.is_changed());
+        assert_eq!(catalog.all().len(), 15);
+        assert_eq!(catalog.next_id(), 16);
+
+        let tag = catalog.create("frontend", None, None, 1_000).unwrap();
+        assert_eq!(tag.id, Some(16));
+        assert_eq!(tag.color, "#6c757d");
+        catalog.delete(16).unwrap();
+        let tag = catalog
+            .create("backend", None, Some("#123abc".to_string()), 1_000)
+            .unwrap();
+        assert_eq!(tag.id, Some(17));
+
+        catalog.toggle(1, 2_000).unwrap();
+        assert_eq!(catalog.list(false).len(), 15);
+        assert_eq!(catalog.list(true).len(), 16);
+    }
+
+    #[test]
+    fn rejects_duplicate_names_and_invalid_colors() {
+        let stored = json!([
+            { "id": 4, "name": "Claims", "description": null, "color": "#ffc107", "active": true, "created_at": null, "updated_at": null },
+            { "id": 9, "name": "Socle", "description": null, "color": "#6c757d", "active": false, "created_at": null, "updated_at": null }
+        ]);
+        let mut catalog =
+            Catalog::from_store(CatalogKind::Projects, Some(stored), Some(json!(12)), None);
+        assert!(!catalog.is_changed());
+        assert_eq!(catalog.next_id(), 12);
+
+        let error = catalog.create(" claims ", None, None, 0).unwrap_err();
+        assert_eq!(error.code, ErrorCode::Conflict);
+        assert_eq!(error.details[0].field, "name");
+        let error = catalog.update(4, "SOCLE", None, None, 0).unwrap_err();
+        assert_eq!(error.code, ErrorCode::Conflict);
+        let error = catalog
+            .create("Portail", None, Some("vert".to_string()), 0)
+            .unwrap_err();
+        assert_eq!(error.details[0].field, "color");
+        assert_eq!(
+            catalog.create("  ", None, None, 0).unwrap_err().code,
+            ErrorCode::Validation
+        );
+        assert_eq!(catalog.toggle(42, 0).unwrap_err().code, ErrorCode::NotFound);
+        assert!(!catalog.is_changed());
+
+        // Renommer un élément en changeant seulement la casse reste possible
+        catalog.update(4, "CLAIMS", None, None, 0).unwrap();
+        assert_eq!(catalog.all()[0].name, "CLAIMS");
+        assert_eq!(catalog.all()[0].updated_at.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn keeps_an_unreadable_catalog_until_it_is_modified() {
+        let catalog = Catalog::from_store(
+            CatalogKind::ActivityTypes,
+            Some(json!({ "broken": true })),
+            None,
+            None,
+        );
+        assert!(!catalog.is_changed());
+        assert_eq!(
+            catalog.all(),
+            CatalogKind::ActivityTypes.defaults().as_slice()
+        );
+        assert_eq!(catalog.next_id(), 9);
+    }
+
+    #[test]
+    fn keeps_old_names_as_aliases_of_the_current_one() {
+        let mut catalog = Catalog::from_store(CatalogKind::Tags, None, None, None);
+        assert_eq!(
+            catalog.rename(1, "bugfix", 0).unwrap(),
+            ("bug".to_string(), "bugfix".to_string())
+        );
+        assert_eq!(catalog.rename(1, "fix", 0).unwrap().0, "bugfix");
+        assert_eq!(
+            catalog.rename(3, "doc s", 0).unwrap_err().code,
+            ErrorCode::Validation
+        );
+
+        // 4 = test, fusionné dans 1 = fix
+        assert_eq!(
+            catalog.merge(4, 1, 0).unwrap(),
+            ("test".to_string(), "fix".to_string())
+        );
+        assert_eq!(
+            catalog.merge(1, 1, 0).unwrap_err().code,
+            ErrorCode::Validation
+        );
+        assert!(catalog.all().iter().all(|tag| tag.id != Some(4)));
+        let aliases = catalog.aliases();
+        assert_eq!(aliases["bug"], "fix");
+        assert_eq!(aliases["bugfix"], "fix");
+        assert_eq!(aliases["test"], "fix");
+
+        // Reprendre un ancien nom le retire des alias
+        catalog.rename(1, "Bug", 0).unwrap();
+        assert!(!catalog.aliases().contains_key("bug"));
+        assert_eq!(catalog.aliases()["fix"], "Bug");
+        assert_eq!(catalog.aliases()["test"], "Bug");
+
+        // Tout comme créer un élément sous un ancien nom
+        catalog.create("Test", None, None, 0).unwrap();
+        assert!(!catalog.aliases().contains_key("test"));
+        assert_eq!(catalog.aliases()["fix"], "Bug");
+
+        let mut entry = ParsedJournalEntry {
+            project: "mandate".to_string(),
+            tags: vec!["test".to_string(), "api".to_string(), "fix".to_string()],
+            ..ParsedJournalEntry::default()
+        };
+        assert!(rename_in_entry(
+            CatalogKind::Tags,
+            &mut entry,
+            "test",
+            "fix"
+        ));
+        assert_eq!(entry.tags, vec!["fix", "api"]);
+        assert!(!rename_in_entry(
+            CatalogKind::Tags,
+            &mut entry,
+            "test",
+            "fix"
+        ));
+        assert!(rename_in_entry(
+            CatalogKind::Projects,
+            &mut entry,
+            "Mandate",
+            "Mandats"
+        ));
+        assert_eq!(entry.project, "Mandats");
+    }
+}
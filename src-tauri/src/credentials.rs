@@ -0,0 +1,315 @@
+use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::jira::OAuthRefresh;
+
+// Identifiants Jira d'un profil, chiffrés au repos : dans le trousseau du
+// système (Secret Service, Trousseau macOS, Gestionnaire d'identification
+// Windows) quand il est disponible, sinon dans un fichier chiffré avec une
+// phrase de passe qui n'est jamais conservée.
+
+const KEYRING_SERVICE: &str = "com.raphaelsalique.dev-journal";
+const FILE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct JiraCredentials {
+    // Email (Cloud) ou identifiant (Server), vide pour un jeton Bearer
+    pub username: String,
+    // Token d'API, mot de passe, jeton personnel ou jeton d'accès OAuth
+    pub secret: String,
+    // De quoi renouveler le jeton d'accès OAuth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<OAuthRefresh>,
+}
+
+// Le secret ne doit jamais apparaître dans les logs
+impl fmt::Debug for JiraCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JiraCredentials")
+            .field("username", &self.username)
+            .field("secret", &"***")
+            .field("oauth", &self.oauth)
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialBackend {
+    Keyring,
+    EncryptedFile,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CredentialStatus {
+    // None si aucun identifiant n'est enregistré
+    pub backend: Option<CredentialBackend>,
+    // Le fichier chiffré ne peut être lu qu'avec la phrase de passe
+    pub needs_passphrase: bool,
+}
+
+// Contenu du fichier chiffré ; la clé est dérivée de la phrase de passe par
+// Argon2id avec un sel propre au fichier
+#[derive(Serialize, Deserialize)]
+struct EncryptedFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Dérivation de la clé impossible : {}", e))?;
+    Ok(key)
+}
+
+fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<EncryptedFile> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| anyhow!("Chiffrement des identifiants impossible"))?;
+
+    Ok(EncryptedFile {
+        version: FILE_VERSION,
+        salt: general_purpose::STANDARD.encode(salt),
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt(file: &EncryptedFile, passphrase: &str) -> Result<Vec<u8>> {
+    if file.version != FILE_VERSION {
+        return Err(anyhow!(
+            "Version du fichier d'identifiants non prise en charge : {}",
+            file.version
+        ));
+    }
+    let salt = general_purpose::STANDARD.decode(&file.salt)?;
+    let nonce = general_purpose::STANDARD.decode(&file.nonce)?;
+    let ciphertext = general_purpose::STANDARD.decode(&file.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err(anyhow!("Fichier d'identifiants corrompu"));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Phrase de passe incorrecte"))
+}
+
+// Identifiants Jira d'un profil
+#[derive(Clone)]
+pub struct CredentialStore {
+    profile_id: String,
+    file: PathBuf,
+    use_keyring: bool,
+}
+
+impl CredentialStore {
+    pub fn new(profile_id: &str, credentials_dir: &Path) -> Self {
+        Self {
+            profile_id: profile_id.to_string(),
+            file: credentials_dir.join(format!("{}.jira.enc", profile_id)),
+            use_keyring: true,
+        }
+    }
+
+    // Sans trousseau, seul le fichier chiffré est utilisé
+    #[cfg(test)]
+    fn without_keyring(mut self) -> Self {
+        self.use_keyring = false;
+        self
+    }
+
+    fn keyring_entry(&self) -> Option<keyring::Entry> {
+        if !self.use_keyring {
+            return None;
+        }
+        keyring::Entry::new(KEYRING_SERVICE, &format!("jira:{}", self.profile_id)).ok()
+    }
+
+    fn read_keyring(&self) -> Option<JiraCredentials> {
+        let password = self.keyring_entry()?.get_password().ok()?;
+        serde_json::from_str(&password).ok()
+    }
+
+    // Enregistre dans le trousseau si possible, sinon dans le fichier chiffré
+    // avec `passphrase`. Un seul emplacement est conservé.
+    pub fn store(
+        &self,
+        credentials: &JiraCredentials,
+        passphrase: Option<&str>,
+    ) -> Result<CredentialBackend> {
+        let serialized = serde_json::to_string(credentials)?;
+
+        if let Some(entry) = self.keyring_entry() {
+            if entry.set_password(&serialized).is_ok() {
+                self.remove_file()?;
+                return Ok(CredentialBackend::Keyring);
+            }
+        }
+
+        let passphrase = passphrase
+            .filter(|passphrase| !passphrase.is_empty())
+            .ok_or_else(|| {
+                anyhow!("Trousseau du système indisponible : une phrase de passe est nécessaire")
+            })?;
+        let file = encrypt(serialized.as_bytes(), passphrase)?;
+        if let Some(dir) = self.file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.file.with_extension("enc.tmp");
+        fs::write(&tmp, serde_json::to_vec(&file)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+        }
+        fs::rename(&tmp, &self.file)?;
+        Ok(CredentialBackend::EncryptedFile)
+    }
+
+    // None si rien n'est enregistré ; erreur si le fichier chiffré demande une
+    // phrase de passe absente ou incorrecte
+    pub fn load(&self, passphrase: Option<&str>) -> Result<Option<JiraCredentials>> {
+        if let Some(credentials) = self.read_keyring() {
+            return Ok(Some(credentials));
+        }
+        if !self.file.exists() {
+            return Ok(None);
+        }
+
+        let passphrase = passphrase
+            .ok_or_else(|| anyhow!("Identifiants Jira verrouillés par une phrase de passe"))?;
+        let file: EncryptedFile = serde_json::from_slice(&fs::read(&self.file)?)?;
+        let plaintext = decrypt(&file, passphrase)?;
+        Ok(Some(serde_json::from_slice(&plaintext)?))
+    }
+
+    pub fn status(&self) -> CredentialStatus {
+        if self.read_keyring().is_some() {
+            CredentialStatus {
+                backend: Some(CredentialBackend::Keyring),
+                needs_passphrase: false,
+            }
+        } else if self.file.exists() {
+            CredentialStatus {
+                backend: Some(CredentialBackend::EncryptedFile),
+                needs_passphrase: true,
+            }
+        } else {
+            CredentialStatus {
+                backend: None,
+                needs_passphrase: false,
+            }
+        }
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        if let Some(entry) = self.keyring_entry() {
+            match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                // Trousseau indisponible : rien n'a pu y être enregistré
+                Err(keyring::Error::NoStorageAccess(_))
+                | Err(keyring::Error::PlatformFailure(_)) => {}
+                Err(e) => return Err(anyhow!("Suppression dans le trousseau impossible : {}", e)),
+            }
+        }
+        self.remove_file()
+    }
+
+    fn remove_file(&self) -> Result<()> {
+        match fs::remove_file(&self.file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, encrypt, CredentialBackend, CredentialStore, JiraCredentials};
+    use crate::jira::OAuthRefresh;
+
+    fn credentials(secret: &str) -> JiraCredentials {
+        JiraCredentials {
+            username: "moi@acme.com".to_string(),
+            secret: secret.to_string(),
+            oauth: None,
+        }
+    }
+
+    #[test]
+    fn encrypts_with_a_passphrase() {
+        let file = encrypt(b"token", "phrase").unwrap();
+
+        assert!(!file.ciphertext.contains("token"));
+        assert_eq!(decrypt(&file, "phrase").unwrap(), b"token");
+        assert_eq!(
+            decrypt(&file, "autre").unwrap_err().to_string(),
+            "Phrase de passe incorrecte"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_an_encrypted_file() {
+        let dir = std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
+        let store = CredentialStore::new("default", &dir).without_keyring();
+
+        assert!(store.store(&credentials("token"), None).is_err());
+        assert_eq!(
+            store.store(&credentials("token"), Some("phrase")).unwrap(),
+            CredentialBackend::EncryptedFile
+        );
+        let content = std::fs::read_to_string(dir.join("default.jira.enc")).unwrap();
+        assert!(!content.contains("token"));
+        assert!(store.status().needs_passphrase);
+        assert!(store.load(None).is_err());
+        assert_eq!(
+            store.load(Some("phrase")).unwrap(),
+            Some(credentials("token"))
+        );
+        assert!(!format!("{:?}", credentials("token")).contains("token"));
+
+        store.clear().unwrap();
+        assert_eq!(store.load(None).unwrap(), None);
+        assert_eq!(store.status().backend, None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_the_oauth_refresh_token() {
+        let dir = std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
+        let store = CredentialStore::new("default", &dir).without_keyring();
+        let credentials = JiraCredentials {
+            oauth: Some(OAuthRefresh {
+                client_id: "app".to_string(),
+                client_secret: "client-secret".to_string(),
+                refresh_token: "refresh".to_string(),
+                expires_at: None,
+            }),
+            ..credentials("access")
+        };
+
+        store.store(&credentials, Some("phrase")).unwrap();
+        assert_eq!(store.load(Some("phrase")).unwrap(), Some(credentials.clone()));
+        let debug = format!("{:?}", credentials);
+        assert!(!debug.contains("client-secret") && !debug.contains("refresh\""));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
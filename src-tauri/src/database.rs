@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
-use tauri_plugin_sql::{Migration, MigrationKind};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Project {
+// Élément d'un catalogue : projet, tag ou type d'activité
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CatalogItem {
     pub id: Option<i64>,
     pub name: String,
     pub description: Option<String>,
@@ -12,122 +12,78 @@ pub struct Project {
     pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Tag {
-    pub id: Option<i64>,
-    pub name: String,
-    pub description: Option<String>,
-    pub color: String,
-    pub active: bool,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
-}
+pub type Project = CatalogItem;
+pub type Tag = CatalogItem;
+pub type ActivityType = CatalogItem;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ActivityType {
-    pub id: Option<i64>,
-    pub name: String,
-    pub description: Option<String>,
-    pub color: String,
-    pub active: bool,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserPreference {
-    pub id: Option<i64>,
-    pub key: String,
-    pub value: Option<String>,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
-}
+// Valeurs par défaut des catalogues (nom, description, couleur)
+pub const DEFAULT_PROJECTS: &[(&str, &str, &str)] = &[
+    ("Mandate", "Gestion de mandats CSE", "#28a745"),
+    ("Instance", "Gestion des réunions", "#007bff"),
+    ("Claims", "Gestion des réclamations", "#ffc107"),
+    ("Negociation", "Module négociations", "#17a2b8"),
+    ("Socle", "Infrastructure technique", "#6c757d"),
+    ("Formation", "Temps de formation et veille", "#e83e8c"),
+    ("Maintenance", "Maintenance et corrections", "#fd7e14"),
+];
 
-pub fn get_migrations() -> Vec<Migration> {
-    vec![
-        Migration {
-            version: 1,
-            description: "create_initial_tables",
-            sql: r#"
-                CREATE TABLE IF NOT EXISTS projects (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    name TEXT NOT NULL UNIQUE,
-                    description TEXT,
-                    color TEXT DEFAULT '#007bff',
-                    active BOOLEAN DEFAULT 1,
-                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-                );
+pub const DEFAULT_TAGS: &[(&str, &str, &str)] = &[
+    ("bug", "Correction de bugs", "#dc3545"),
+    ("feature", "Nouvelle fonctionnalité", "#28a745"),
+    ("refactor", "Refactoring de code", "#6f42c1"),
+    ("test", "Tests et QA", "#20c997"),
+    ("documentation", "Documentation", "#0dcaf0"),
+    ("release", "Préparation de release", "#fd7e14"),
+    ("meeting", "Réunions et discussions", "#6c757d"),
+    ("review", "Code review", "#e83e8c"),
+    ("performance", "Optimisation performance", "#ffc107"),
+    ("security", "Sécurité", "#dc3545"),
+    ("deployment", "Déploiement", "#198754"),
+    ("research", "Recherche et POC", "#0d6efd"),
+    ("maintenance", "Maintenance technique", "#fd7e14"),
+    ("support", "Support utilisateur", "#6610f2"),
+    ("planning", "Planification et estimation", "#6f42c1"),
+];
 
-                CREATE TABLE IF NOT EXISTS tags (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    name TEXT NOT NULL UNIQUE,
-                    description TEXT,
-                    color TEXT DEFAULT '#6c757d',
-                    active BOOLEAN DEFAULT 1,
-                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-                );
-
-                CREATE TABLE IF NOT EXISTS user_preferences (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    key TEXT NOT NULL UNIQUE,
-                    value TEXT,
-                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-                );
-
-                CREATE TRIGGER IF NOT EXISTS update_projects_timestamp 
-                AFTER UPDATE ON projects
-                BEGIN
-                    UPDATE projects SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
-                END;
-
-                CREATE TRIGGER IF NOT EXISTS update_tags_timestamp 
-                AFTER UPDATE ON tags
-                BEGIN
-                    UPDATE tags SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
-                END;
-
-                CREATE TRIGGER IF NOT EXISTS update_preferences_timestamp 
-                AFTER UPDATE ON user_preferences
-                BEGIN
-                    UPDATE user_preferences SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
-                END;
-            "#,
-            kind: MigrationKind::Up,
-        },
-        Migration {
-            version: 2,
-            description: "seed_default_data",
-            sql: r#"
-                INSERT OR IGNORE INTO projects (name, description, color) VALUES
-                ('Mandate', 'Gestion de mandats CSE', '#28a745'),
-                ('Instance', 'Gestion des réunions', '#007bff'),
-                ('Claims', 'Gestion des réclamations', '#ffc107'),
-                ('Negociation', 'Module négociations', '#17a2b8'),
-                ('Socle', 'Infrastructure technique', '#6c757d'),
-                ('Formation', 'Temps de formation et veille', '#e83e8c'),
-                ('Maintenance', 'Maintenance et corrections', '#fd7e14');
-
-                INSERT OR IGNORE INTO tags (name, description, color) VALUES
-                ('bug', 'Correction de bugs', '#dc3545'),
-                ('feature', 'Nouvelle fonctionnalité', '#28a745'),
-                ('refactor', 'Refactoring de code', '#6f42c1'),
-                ('test', 'Tests et QA', '#20c997'),
-                ('documentation', 'Documentation', '#0dcaf0'),
-                ('release', 'Préparation de release', '#fd7e14'),
-                ('meeting', 'Réunions et discussions', '#6c757d'),
-                ('review', 'Code review', '#e83e8c'),
-                ('performance', 'Optimisation performance', '#ffc107'),
-                ('security', 'Sécurité', '#dc3545'),
-                ('deployment', 'Déploiement', '#198754'),
-                ('research', 'Recherche et POC', '#0d6efd'),
-                ('maintenance', 'Maintenance technique', '#fd7e14'),
-                ('support', 'Support utilisateur', '#6610f2'),
-                ('planning', 'Planification et estimation', '#6f42c1');
-            "#,
-            kind: MigrationKind::Up,
-        },
-    ]
-}
+pub const DEFAULT_ACTIVITY_TYPES: &[(&str, &str, &str)] = &[
+    (
+        "debug",
+        "Investigation et correction de probleme",
+        "#dc3545",
+    ),
+    (
+        "développement",
+        "Implementation de fonctionnalites",
+        "#007bff",
+    ),
+    (
+        "documentation",
+        "Redaction et mise a jour de documentation",
+        "#17a2b8",
+    ),
+    (
+        "formation",
+        "Apprentissage et montee en competence",
+        "#6f42c1",
+    ),
+    (
+        "infrastructure",
+        "Outillage, CI, environnements et operations",
+        "#6c757d",
+    ),
+    (
+        "réunion",
+        "Synchronisation, atelier ou point d'equipe",
+        "#ffc107",
+    ),
+    (
+        "revue de code",
+        "Lecture et validation de modifications",
+        "#28a745",
+    ),
+    (
+        "veille technologique",
+        "Exploration et suivi technique",
+        "#fd7e14",
+    ),
+];
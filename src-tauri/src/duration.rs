@@ -0,0 +1,335 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+
+// Suffixe ajouté par l'ancien format à la saisie brute ("5 minutes", "1h30 minutes")
+const LEGACY_SUFFIX: &str = " minutes";
+
+// En dessous de ce seuil, les anciens rapports comptaient un nombre nu en heures
+const LEGACY_HOURS_THRESHOLD: u32 = 10;
+
+fn hours_minutes_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"^(\d+(?:\.\d+)?)\s*(?:h|hr|hrs|heure|heures|hour|hours)(?:\s*(\d+)\s*(?:m|mn|min|mins|minute|minutes)?)?$",
+        )
+        .unwrap()
+    })
+}
+
+fn minutes_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^(\d+)\s*(?:m|mn|min|mins|minute|minutes)$").unwrap())
+}
+
+fn iso_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^pt(?:(\d+(?:\.\d+)?)h)?(?:(\d+)m)?$").unwrap())
+}
+
+fn bare_number_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^\d+(?:\.\d+)?$").unwrap())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationError {
+    Empty,
+    // Nombre sans unité : minutes ou heures ?
+    Ambiguous,
+    Invalid,
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationError::Empty => write!(f, "Durée manquante"),
+            DurationError::Ambiguous => {
+                write!(f, "Durée ambiguë : précisez l'unité (ex. 90min ou 1h30)")
+            }
+            DurationError::Invalid => write!(
+                f,
+                "Durée invalide (formats acceptés : 1h30, 90m, 1.5h, PT1H30M)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+// Durée d'une entrée, stockée en minutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Duration {
+    minutes: u32,
+}
+
+impl Duration {
+    pub fn from_minutes(minutes: u32) -> Self {
+        Self { minutes }
+    }
+
+    pub fn minutes(self) -> u32 {
+        self.minutes
+    }
+
+    fn from_hours(hours: &str, minutes: Option<&str>) -> Result<Self, DurationError> {
+        let minutes: u32 = match minutes {
+            Some(minutes) => minutes.parse().map_err(|_| DurationError::Invalid)?,
+            None => 0,
+        };
+        if minutes != 0 && (hours.contains('.') || minutes >= 60) {
+            return Err(DurationError::Invalid);
+        }
+
+        let hours: f64 = hours.parse().map_err(|_| DurationError::Invalid)?;
+        let total = (hours * 60.0).round() + minutes as f64;
+        if total > u32::MAX as f64 {
+            return Err(DurationError::Invalid);
+        }
+        Ok(Self::from_minutes(total as u32))
+    }
+
+    // Formats acceptés : "1h30", "1h 30min", "2h", "1.5h", "1,5 h", "90m",
+    // "90 min", "PT1H30M". Un nombre sans unité est refusé comme ambigu.
+    pub fn parse(input: &str) -> Result<Self, DurationError> {
+        let text = input.trim().to_lowercase().replace(',', ".");
+        if text.is_empty() {
+            return Err(DurationError::Empty);
+        }
+
+        if let Some(captures) = hours_minutes_regex().captures(&text) {
+            return Self::from_hours(&captures[1], captures.get(2).map(|m| m.as_str()));
+        }
+        if let Some(captures) = minutes_regex().captures(&text) {
+            return captures[1]
+                .parse()
+                .map(Self::from_minutes)
+                .map_err(|_| DurationError::Invalid);
+        }
+        if let Some(captures) = iso_regex().captures(&text) {
+            return match (captures.get(1), captures.get(2)) {
+                (None, None) => Err(DurationError::Invalid),
+                (Some(hours), minutes) => {
+                    Self::from_hours(hours.as_str(), minutes.map(|m| m.as_str()))
+                }
+                (None, Some(minutes)) => minutes
+                    .as_str()
+                    .parse()
+                    .map(Self::from_minutes)
+                    .map_err(|_| DurationError::Invalid),
+            };
+        }
+        if bare_number_regex().is_match(&text) {
+            return Err(DurationError::Ambiguous);
+        }
+
+        Err(DurationError::Invalid)
+    }
+}
+
+// Forme canonique, identique à celle calculée par le formulaire depuis la
+// plage horaire : "45min", "2h", "1h05"
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (hours, minutes) = (self.minutes / 60, self.minutes % 60);
+        match (hours, minutes) {
+            (0, minutes) => write!(f, "{}min", minutes),
+            (hours, 0) => write!(f, "{}h", hours),
+            (hours, minutes) => write!(f, "{}h{:02}", hours, minutes),
+        }
+    }
+}
+
+// Unité à retenir pour les anciennes durées ambiguës lors de la migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmbiguousUnit {
+    Hours,
+    Minutes,
+}
+
+// Durée telle qu'écrite dans une entrée du journal. Une valeur illisible est
+// conservée telle quelle pour ne rien perdre et pour être signalée.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum EntryDuration {
+    Known(Duration),
+    Unparsed { text: String, error: DurationError },
+}
+
+impl Default for EntryDuration {
+    fn default() -> Self {
+        EntryDuration::Unparsed {
+            text: String::new(),
+            error: DurationError::Empty,
+        }
+    }
+}
+
+impl EntryDuration {
+    // Lecture tolérante aux valeurs écrites par l'ancien format
+    pub fn from_text(text: &str) -> Self {
+        let text = text.trim();
+
+        if let Some(raw) = text.strip_suffix(LEGACY_SUFFIX) {
+            match Duration::parse(raw) {
+                Ok(duration) => return EntryDuration::Known(duration),
+                // Seuls 0 et les nombres à partir de 10 étaient déjà comptés
+                // en minutes par les anciens rapports
+                Err(DurationError::Ambiguous) => match raw.trim().parse::<u32>() {
+                    Ok(minutes) if minutes == 0 || minutes >= LEGACY_HOURS_THRESHOLD => {
+                        return EntryDuration::Known(Duration::from_minutes(minutes))
+                    }
+                    _ => {
+                        return EntryDuration::Unparsed {
+                            text: text.to_string(),
+                            error: DurationError::Ambiguous,
+                        }
+                    }
+                },
+                Err(_) => {}
+            }
+        }
+
+        match Duration::parse(text) {
+            Ok(duration) => EntryDuration::Known(duration),
+            Err(error) => EntryDuration::Unparsed {
+                text: text.to_string(),
+                error,
+            },
+        }
+    }
+
+    pub fn known(&self) -> Option<Duration> {
+        match self {
+            EntryDuration::Known(duration) => Some(*duration),
+            EntryDuration::Unparsed { .. } => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<DurationError> {
+        match self {
+            EntryDuration::Known(_) => None,
+            EntryDuration::Unparsed { error, .. } => Some(*error),
+        }
+    }
+
+    // Interprète une ancienne valeur ambiguë dans l'unité choisie
+    pub fn resolve_ambiguous(&self, unit: AmbiguousUnit) -> Option<Duration> {
+        let EntryDuration::Unparsed {
+            text,
+            error: DurationError::Ambiguous,
+        } = self
+        else {
+            return None;
+        };
+
+        let raw = text.strip_suffix(LEGACY_SUFFIX).unwrap_or(text);
+        let value: f64 = raw.trim().replace(',', ".").parse().ok()?;
+        let minutes = match unit {
+            AmbiguousUnit::Hours => value * 60.0,
+            AmbiguousUnit::Minutes => value,
+        };
+        Some(Duration::from_minutes(minutes.round() as u32))
+    }
+}
+
+impl fmt::Display for EntryDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryDuration::Known(duration) => duration.fmt(f),
+            EntryDuration::Unparsed { text, .. } => f.write_str(text),
+        }
+    }
+}
+
+impl From<String> for EntryDuration {
+    fn from(text: String) -> Self {
+        EntryDuration::from_text(&text)
+    }
+}
+
+impl From<EntryDuration> for String {
+    fn from(duration: EntryDuration) -> Self {
+        duration.to_string()
+    }
+}
+
+// Entrée dont la durée n'a pas pu être comptée
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DurationIssue {
+    pub date: String,
+    pub entry_id: String,
+    pub timestamp: String,
+    pub value: String,
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AmbiguousUnit, Duration, DurationError, EntryDuration};
+
+    fn minutes(input: &str) -> Result<u32, DurationError> {
+        Duration::parse(input).map(Duration::minutes)
+    }
+
+    #[test]
+    fn parses_supported_formats() {
+        assert_eq!(minutes("1h30"), Ok(90));
+        assert_eq!(minutes("1h 30min"), Ok(90));
+        assert_eq!(minutes("90m"), Ok(90));
+        assert_eq!(minutes("90 minutes"), Ok(90));
+        assert_eq!(minutes("1.5h"), Ok(90));
+        assert_eq!(minutes("1,5 h"), Ok(90));
+        assert_eq!(minutes("PT1H30M"), Ok(90));
+        assert_eq!(minutes("PT45M"), Ok(45));
+        assert_eq!(minutes("2 heures"), Ok(120));
+    }
+
+    #[test]
+    fn rejects_ambiguous_and_invalid_values() {
+        assert_eq!(minutes("5"), Err(DurationError::Ambiguous));
+        assert_eq!(minutes("1.5"), Err(DurationError::Ambiguous));
+        assert_eq!(minutes(""), Err(DurationError::Empty));
+        assert_eq!(minutes("1h75"), Err(DurationError::Invalid));
+        assert_eq!(minutes("1.5h30"), Err(DurationError::Invalid));
+        assert_eq!(minutes("PT"), Err(DurationError::Invalid));
+        assert_eq!(minutes("une heure"), Err(DurationError::Invalid));
+    }
+
+    #[test]
+    fn formats_canonically() {
+        for (value, expected) in [(0, "0min"), (45, "45min"), (120, "2h"), (65, "1h05")] {
+            let duration = Duration::from_minutes(value);
+            assert_eq!(duration.to_string(), expected);
+            assert_eq!(Duration::parse(expected), Ok(duration));
+        }
+    }
+
+    #[test]
+    fn reads_legacy_values() {
+        assert_eq!(
+            EntryDuration::from_text("45 minutes").known(),
+            Some(Duration::from_minutes(45))
+        );
+        assert_eq!(
+            EntryDuration::from_text("1h30 minutes").known(),
+            Some(Duration::from_minutes(90))
+        );
+
+        let ambiguous = EntryDuration::from_text("5 minutes");
+        assert_eq!(ambiguous.error(), Some(DurationError::Ambiguous));
+        assert_eq!(ambiguous.to_string(), "5 minutes");
+        assert_eq!(
+            ambiguous.resolve_ambiguous(AmbiguousUnit::Hours),
+            Some(Duration::from_minutes(300))
+        );
+        assert_eq!(
+            ambiguous.resolve_ambiguous(AmbiguousUnit::Minutes),
+            Some(Duration::from_minutes(5))
+        );
+    }
+}
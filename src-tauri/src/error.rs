@@ -0,0 +1,249 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::PoisonError;
+
+// Préférence choisissant la langue des messages d'erreur ("fr" ou "en")
+pub const LOCALE_PREFERENCE: &str = "locale";
+
+static ENGLISH: AtomicBool = AtomicBool::new(false);
+
+pub fn set_locale(locale: &str) {
+    ENGLISH.store(locale.trim().eq_ignore_ascii_case("en"), Ordering::Relaxed);
+}
+
+// Codes stables transmis au frontend : ils ne dépendent ni de la langue ni de
+// la formulation des messages
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    NotFound,
+    Validation,
+    Conflict,
+    Io,
+    JiraNotConfigured,
+    JiraAuth,
+    JiraRateLimited,
+    JiraUnavailable,
+    Jira,
+    Internal,
+}
+
+impl ErrorCode {
+    // Messages génériques des traductions ; le message français d'origine est
+    // plus précis et reste celui par défaut
+    fn english(self) -> &'static str {
+        match self {
+            ErrorCode::NotFound => "The requested item was not found",
+            ErrorCode::Validation => "Some values are invalid",
+            ErrorCode::Conflict => "The item was changed or already exists",
+            ErrorCode::Io => "A file could not be read or written",
+            ErrorCode::JiraNotConfigured => "Jira is not configured",
+            ErrorCode::JiraAuth => "Jira rejected the credentials",
+            ErrorCode::JiraRateLimited => "Too many Jira requests, try again later",
+            ErrorCode::JiraUnavailable => "Jira is unreachable",
+            ErrorCode::Jira => "Jira returned an error",
+            ErrorCode::Internal => "Unexpected error",
+        }
+    }
+}
+
+// Erreur d'un champ saisi, pour l'afficher à côté du champ
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+// Erreur renvoyée par les commandes. Elle peut aussi traverser les modules
+// dans un `anyhow::Error` : elle est alors retrouvée telle quelle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Vec<FieldError>,
+    // Délai demandé par Jira avant un nouvel essai, en secondes
+    pub retry_after: Option<u64>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: Vec::new(),
+            retry_after: None,
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    // Erreur de validation portant sur un seul champ
+    pub fn invalid_field(field: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self::validation(message.clone()).with_field(field, message)
+    }
+
+    pub fn with_field(mut self, field: &str, message: impl Into<String>) -> Self {
+        self.details.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_retry_after(mut self, seconds: Option<u64>) -> Self {
+        self.retry_after = seconds;
+        self
+    }
+
+    fn localized_message(&self) -> &str {
+        if ENGLISH.load(Ordering::Relaxed) {
+            self.code.english()
+        } else {
+            &self.message
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("AppError", 4)?;
+        error.serialize_field("code", &self.code)?;
+        error.serialize_field("message", self.localized_message())?;
+        error.serialize_field("details", &self.details)?;
+        error.serialize_field("retry_after", &self.retry_after)?;
+        error.end()
+    }
+}
+
+fn io_code(error: &std::io::Error) -> ErrorCode {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+        _ => ErrorCode::Io,
+    }
+}
+
+fn http_code(error: &reqwest::Error) -> ErrorCode {
+    if error.is_timeout() || error.is_connect() {
+        ErrorCode::JiraUnavailable
+    } else {
+        ErrorCode::Jira
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(io_code(&error), error.to_string())
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::new(http_code(&error), error.to_string())
+    }
+}
+
+// Les erreurs déjà typées par un module sont reprises telles quelles ; les
+// autres sont classées d'après leur cause
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<AppError>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+        let code = error
+            .chain()
+            .find_map(|cause| {
+                cause
+                    .downcast_ref::<std::io::Error>()
+                    .map(io_code)
+                    .or_else(|| cause.downcast_ref::<reqwest::Error>().map(http_code))
+            })
+            .unwrap_or(ErrorCode::Internal);
+        Self::new(code, error.to_string())
+    }
+}
+
+impl From<tauri_plugin_store::Error> for AppError {
+    fn from(error: tauri_plugin_store::Error) -> Self {
+        Self::new(ErrorCode::Io, error.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(error: tauri::Error) -> Self {
+        Self::internal(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::internal(error.to_string())
+    }
+}
+
+impl<T> From<PoisonError<T>> for AppError {
+    fn from(error: PoisonError<T>) -> Self {
+        Self::internal(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppError, ErrorCode};
+
+    #[test]
+    fn keeps_typed_errors_through_anyhow() {
+        let typed: anyhow::Error = AppError::invalid_field("date", "Date invalide").into();
+        // Le contexte ajouté en chemin n'efface pas le type d'origine
+        let error = AppError::from(typed.context("Chargement du journal"));
+
+        assert_eq!(error.code, ErrorCode::Validation);
+        assert_eq!(error.details[0].field, "date");
+
+        let missing = std::fs::read("/nonexistent/dev-journal").unwrap_err();
+        assert_eq!(
+            AppError::from(anyhow::Error::new(missing)).code,
+            ErrorCode::NotFound
+        );
+    }
+
+    #[test]
+    fn serializes_a_stable_code() {
+        let error = AppError::new(ErrorCode::JiraRateLimited, "Trop de requêtes Jira")
+            .with_retry_after(Some(30));
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "JiraRateLimited",
+                "message": "Trop de requêtes Jira",
+                "details": [],
+                "retry_after": 30
+            })
+        );
+    }
+}
@@ -1,7 +1,21 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crate::duration::{AmbiguousUnit, Duration, DurationError, DurationIssue, EntryDuration};
+use crate::error::AppError;
+use crate::markdown::{
+    generate_markdown_entry, has_non_canonical_durations, parse_journal_document,
+    render_journal_document, rewrite_jira_links, JiraTicketRef, Link, ParsedJournalEntry,
+    WorklogRef, ENTRY_SEPARATOR,
+};
+use crate::time_range::{effective_duration, TimeRange};
+use crate::timestamp::{entry_timestamp, TimestampFormat};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JournalEntry {
@@ -19,35 +33,24 @@ pub struct JournalEntry {
     pub jira_tickets: Vec<JiraTicketRef>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Link {
-    pub text: String,
-    pub url: String,
-}
-
-pub fn get_journal_dir() -> Result<PathBuf> {
+// Dossier du journal du profil par défaut
+pub fn default_journal_dir() -> Result<PathBuf> {
     let home_dir = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Impossible de trouver le répertoire home"))?;
-    let journal_dir = home_dir.join("Documents").join("DevJournal");
-
-    if !journal_dir.exists() {
-        fs::create_dir_all(&journal_dir)?;
-    }
-
-    Ok(journal_dir)
+    Ok(home_dir.join("Documents").join("DevJournal"))
 }
 
 fn build_journal_file_path(journal_dir: &Path, date: &str) -> Result<PathBuf> {
     let mut parts = date.split('-');
     let year = parts
         .next()
-        .ok_or_else(|| anyhow::anyhow!("Date invalide: année manquante"))?;
+        .ok_or_else(|| AppError::invalid_field("date", "Date invalide: année manquante"))?;
     let month = parts
         .next()
-        .ok_or_else(|| anyhow::anyhow!("Date invalide: mois manquant"))?;
+        .ok_or_else(|| AppError::invalid_field("date", "Date invalide: mois manquant"))?;
     let day = parts
         .next()
-        .ok_or_else(|| anyhow::anyhow!("Date invalide: jour manquant"))?;
+        .ok_or_else(|| AppError::invalid_field("date", "Date invalide: jour manquant"))?;
 
     if parts.next().is_some()
         || year.len() != 4
@@ -57,9 +60,9 @@ fn build_journal_file_path(journal_dir: &Path, date: &str) -> Result<PathBuf> {
         || !month.chars().all(|c| c.is_ascii_digit())
         || !day.chars().all(|c| c.is_ascii_digit())
     {
-        return Err(anyhow::anyhow!(
-            "Format de date invalide: attendu YYYY-MM-DD"
-        ));
+        return Err(
+            AppError::invalid_field("date", "Format de date invalide: attendu YYYY-MM-DD").into(),
+        );
     }
 
     Ok(journal_dir
@@ -68,13 +71,36 @@ fn build_journal_file_path(journal_dir: &Path, date: &str) -> Result<PathBuf> {
         .join(format!("{}.md", date)))
 }
 
-fn collect_journal_dates_in_dir(dir: &Path, dates: &mut Vec<String>) -> Result<()> {
+// Fichier d'un jour du journal, avec de quoi détecter qu'il a changé
+#[derive(Debug, Clone)]
+pub struct JournalFile {
+    pub date: String,
+    pub path: PathBuf,
+    pub fingerprint: FileFingerprint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileFingerprint {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+}
+
+impl FileFingerprint {
+    pub fn of(metadata: &fs::Metadata) -> Self {
+        Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        }
+    }
+}
+
+fn collect_journal_files_in_dir(dir: &Path, files: &mut Vec<JournalFile>) -> Result<()> {
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
 
         if path.is_dir() {
-            collect_journal_dates_in_dir(&path, dates)?;
+            collect_journal_files_in_dir(&path, files)?;
             continue;
         }
 
@@ -84,7 +110,11 @@ fn collect_journal_dates_in_dir(dir: &Path, dates: &mut Vec<String>) -> Result<(
 
         if let Some(date_str) = path.file_stem().and_then(|stem| stem.to_str()) {
             if date_str.len() == 10 && date_str.matches('-').count() == 2 {
-                dates.push(date_str.to_string());
+                files.push(JournalFile {
+                    date: date_str.to_string(),
+                    fingerprint: FileFingerprint::of(&entry.metadata()?),
+                    path,
+                });
             }
         }
     }
@@ -92,33 +122,121 @@ fn collect_journal_dates_in_dir(dir: &Path, dates: &mut Vec<String>) -> Result<(
     Ok(())
 }
 
-pub fn save_journal_entry(date: &str, entry: JournalEntry) -> Result<()> {
-    let journal_dir = get_journal_dir()?;
-    let file_path = build_journal_file_path(&journal_dir, date)?;
+fn collect_journal_dates_in_dir(dir: &Path, dates: &mut Vec<String>) -> Result<()> {
+    let mut files = Vec::new();
+    collect_journal_files_in_dir(dir, &mut files)?;
+    dates.extend(files.into_iter().map(|file| file.date));
+    Ok(())
+}
+
+// Verrou par fichier : les commandes Tauri concurrentes qui lisent, modifient
+// puis réécrivent le même jour passent l'une après l'autre.
+fn journal_file_lock(file_path: &Path) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    locks.entry(file_path.to_path_buf()).or_default().clone()
+}
+
+fn backup_file_path(file_path: &Path) -> PathBuf {
+    let mut backup = file_path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+// Écrit dans un fichier temporaire du même dossier, le synchronise sur disque
+// puis le renomme : le fichier cible contient toujours l'ancienne ou la
+// nouvelle version complète, jamais une version tronquée.
+fn write_file_atomically(file_path: &Path, content: &[u8]) -> Result<()> {
+    let parent_dir = file_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Impossible de déterminer le dossier parent du journal"))?;
+    let file_name = file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Nom de fichier journal invalide"))?;
+    let temp_path = parent_dir.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+
+    let write_result = (|| -> Result<()> {
+        let mut temp_file = fs::File::create(&temp_path)?;
+        temp_file.write_all(content)?;
+        temp_file.sync_all()?;
+        fs::rename(&temp_path, file_path)?;
+        Ok(())
+    })();
+
+    if write_result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return write_result;
+    }
+
+    // Rendre le renommage durable (non supporté sous Windows)
+    #[cfg(unix)]
+    fs::File::open(parent_dir)?.sync_all()?;
+
+    Ok(())
+}
+
+// Remplace le contenu d'un fichier journal en conservant la version précédente
+// dans `<fichier>.bak`. Doit être appelé avec le verrou du fichier.
+fn write_journal_file(file_path: &Path, content: &str) -> Result<()> {
+    if file_path.exists() {
+        let previous = fs::read(file_path)?;
+        write_file_atomically(&backup_file_path(file_path), &previous)?;
+    }
+
+    write_file_atomically(file_path, content.as_bytes())
+}
+
+// Restaure la sauvegarde d'un fichier journal. La version courante devient à
+// son tour la sauvegarde, ce qui permet d'annuler la restauration.
+fn restore_journal_file(file_path: &Path) -> Result<()> {
+    let backup_path = backup_file_path(file_path);
+    if !backup_path.exists() {
+        return Err(AppError::not_found("Aucune sauvegarde disponible pour ce jour").into());
+    }
+
+    let backup = fs::read_to_string(&backup_path)?;
+    write_journal_file(file_path, &backup)
+}
+
+pub fn save_journal_entry(
+    journal_dir: &Path,
+    date: &str,
+    entry: JournalEntry,
+    timestamp_format: TimestampFormat,
+    jira_base_url: Option<&str>,
+) -> Result<()> {
+    let file_path = build_journal_file_path(journal_dir, date)?;
     let parent_dir = file_path
         .parent()
         .ok_or_else(|| anyhow::anyhow!("Impossible de déterminer le dossier parent du journal"))?;
     fs::create_dir_all(parent_dir)?;
 
-    let content = format_entry_as_markdown(&entry);
+    let content = format_entry_as_markdown(&entry, date, timestamp_format, jira_base_url)?;
+
+    let lock = journal_file_lock(&file_path);
+    let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
 
     if file_path.exists() {
         // Append to existing file
         let mut existing_content = fs::read_to_string(&file_path)?;
-        existing_content.push_str("\n\n---\n\n");
+        if !existing_content.ends_with('\n') {
+            existing_content.push('\n');
+        }
+        existing_content.push_str(ENTRY_SEPARATOR);
         existing_content.push_str(&content);
-        fs::write(&file_path, existing_content)?;
+        write_journal_file(&file_path, &existing_content)
     } else {
         // Create new file
-        fs::write(&file_path, content)?;
+        write_journal_file(&file_path, &content)
     }
-
-    Ok(())
 }
 
-pub fn load_journal_file(date: &str) -> Result<String> {
-    let journal_dir = get_journal_dir()?;
-    let file_path = build_journal_file_path(&journal_dir, date)?;
+pub fn load_journal_file(journal_dir: &Path, date: &str) -> Result<String> {
+    let file_path = build_journal_file_path(journal_dir, date)?;
 
     if file_path.exists() {
         Ok(fs::read_to_string(file_path)?)
@@ -127,376 +245,237 @@ pub fn load_journal_file(date: &str) -> Result<String> {
     }
 }
 
-pub fn get_available_journal_dates() -> Result<Vec<String>> {
-    let journal_dir = get_journal_dir()?;
+// Une entrée du jour par son identifiant
+pub fn load_journal_entry(
+    journal_dir: &Path,
+    date: &str,
+    entry_id: &str,
+) -> Result<ParsedJournalEntry> {
+    parse_journal_document(&load_journal_file(journal_dir, date)?)
+        .entries
+        .into_iter()
+        .find(|entry| entry.id == entry_id)
+        .ok_or_else(|| entry_not_found(entry_id))
+}
+
+pub fn list_journal_files(journal_dir: &Path) -> Result<Vec<JournalFile>> {
+    if !journal_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut files = Vec::new();
+    collect_journal_files_in_dir(journal_dir, &mut files)?;
+    Ok(files)
+}
 
+pub fn get_available_journal_dates(journal_dir: &Path) -> Result<Vec<String>> {
     if !journal_dir.exists() {
         return Ok(vec![]);
     }
 
     let mut dates = Vec::new();
-    collect_journal_dates_in_dir(&journal_dir, &mut dates)?;
+    collect_journal_dates_in_dir(journal_dir, &mut dates)?;
 
     dates.sort();
     dates.reverse(); // Plus récent en premier
     Ok(dates)
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ParsedJournalEntry {
-    pub timestamp: String,
-    pub project: String,
-    pub description: String,
-    pub duration: String,
-    pub tags: Vec<String>,
-    pub time_range: String,
-    pub entry_type: String,
-    pub results: String,
-    pub blockers: String,
-    pub links: Vec<Link>,
-    pub reflections: String,
-    pub jira_tickets: Vec<JiraTicketRef>,
+// Entrées des jours compris entre `start_date` et `end_date` inclus, par jour
+pub fn load_journal_entries_between(
+    journal_dir: &Path,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<(String, Vec<ParsedJournalEntry>)>> {
+    let mut files: Vec<JournalFile> = list_journal_files(journal_dir)?
+        .into_iter()
+        .filter(|file| file.date.as_str() >= start_date && file.date.as_str() <= end_date)
+        .collect();
+    files.sort_by(|a, b| a.date.cmp(&b.date));
+
+    files
+        .into_iter()
+        .map(|file| {
+            let content = fs::read_to_string(&file.path)?;
+            Ok((file.date, parse_journal_document(&content).entries))
+        })
+        .collect()
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct JiraTicketRef {
-    pub key: String,
-    pub summary: Option<String>,
-}
-
-// Parse les entrées d'un fichier journal
-pub fn parse_journal_entries(content: &str) -> Vec<ParsedJournalEntry> {
-    if content.is_empty() {
-        return vec![];
-    }
-
-    let mut entries = Vec::new();
-    let sections: Vec<&str> = content.split("## ").collect();
-
-    for section in sections {
-        if section.trim().is_empty() {
-            continue;
-        }
-
-        let lines: Vec<&str> = section.lines().collect();
-        if lines.is_empty() {
-            continue;
-        }
+// Remplace les worklogs synchronisés des entrées d'un jour, par identifiant
+// d'entrée. Les entrées supprimées depuis sont ignorées.
+pub fn set_entry_worklogs(
+    journal_dir: &Path,
+    date: &str,
+    worklogs: &HashMap<String, Vec<WorklogRef>>,
+) -> Result<()> {
+    let file_path = build_journal_file_path(journal_dir, date)?;
 
-        let timestamp = lines[0].trim().to_string();
-        if timestamp.is_empty() {
-            continue;
-        }
+    let lock = journal_file_lock(&file_path);
+    let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
 
-        let mut entry = ParsedJournalEntry {
-            timestamp,
-            project: String::new(),
-            description: String::new(),
-            duration: "0".to_string(),
-            tags: Vec::new(),
-            time_range: String::new(),
-            entry_type: "développement".to_string(),
-            results: String::new(),
-            blockers: String::new(),
-            links: Vec::new(),
-            reflections: String::new(),
-            jira_tickets: Vec::new(),
-        };
-
-        let mut i = 1;
-        while i < lines.len() {
-            let line = lines[i].trim();
-
-            if line.starts_with("**Projet**:") {
-                entry.project = line.replace("**Projet**:", "").trim().to_string();
-            } else if line.starts_with("**Description**:") {
-                let mut description = line.replace("**Description**:", "").trim().to_string();
-                i += 1;
-                while i < lines.len() && !lines[i].starts_with("**") {
-                    if !lines[i].trim().is_empty() {
-                        if !description.is_empty() {
-                            description.push('\n');
-                        }
-                        description.push_str(lines[i]);
-                    }
-                    i += 1;
-                }
-                entry.description = description;
-                continue;
-            } else if line.starts_with("**Durée**:") {
-                entry.duration = line
-                    .replace("**Durée**:", "")
-                    .replace("minutes", "")
-                    .trim()
-                    .to_string();
-            } else if line.starts_with("**Tags**:") {
-                let tags_replaced = line.replace("**Tags**:", "");
-                let tags_str = tags_replaced.trim();
-                entry.tags = if tags_str == "Aucun" || tags_str.is_empty() {
-                    Vec::new()
-                } else {
-                    tags_str
-                        .split_whitespace()
-                        .map(|tag| tag.replace('#', ""))
-                        .filter(|tag| !tag.is_empty())
-                        .collect()
-                };
-            } else if line.starts_with("**Plage horaire**:") {
-                entry.time_range = line.replace("**Plage horaire**:", "").trim().to_string();
-            } else if line.starts_with("**Type d'activité**:") {
-                entry.entry_type = line.replace("**Type d'activité**:", "").trim().to_string();
-            } else if line.starts_with("**Résultats**:") {
-                let mut results = line.replace("**Résultats**:", "").trim().to_string();
-                i += 1;
-                while i < lines.len() && !lines[i].starts_with("**") {
-                    if !lines[i].trim().is_empty() {
-                        if !results.is_empty() {
-                            results.push('\n');
-                        }
-                        results.push_str(lines[i]);
-                    }
-                    i += 1;
-                }
-                entry.results = results;
-                continue;
-            } else if line.starts_with("**Blocages**:") {
-                let mut blockers = line.replace("**Blocages**:", "").trim().to_string();
-                i += 1;
-                while i < lines.len() && !lines[i].starts_with("**") {
-                    if !lines[i].trim().is_empty() {
-                        if !blockers.is_empty() {
-                            blockers.push('\n');
-                        }
-                        blockers.push_str(lines[i]);
-                    }
-                    i += 1;
-                }
-                entry.blockers = blockers;
-                continue;
-            } else if line.starts_with("**Réflexions**:") {
-                let mut reflections = line.replace("**Réflexions**:", "").trim().to_string();
-                i += 1;
-                while i < lines.len() && !lines[i].starts_with("**") {
-                    if !lines[i].trim().is_empty() {
-                        if !reflections.is_empty() {
-                            reflections.push('\n');
-                        }
-                        reflections.push_str(lines[i]);
-                    }
-                    i += 1;
-                }
-                entry.reflections = reflections;
-                continue;
-            } else if line.starts_with("**Liens**:") {
-                let line_replaced = line.replace("**Liens**:", "");
-                let links_str = line_replaced.trim();
-                if !links_str.is_empty() && links_str != "Aucun" {
-                    // Parse d'abord les tickets Jira [KEY - SUMMARY](URL) ou [KEY](URL)
-                    let jira_regex = regex::Regex::new(
-                        r"(\[([A-Z]+-\d+)(?:\s*-\s*([^\]]+))?\]\([^)]*browse/([^)]+)\))",
-                    )
-                    .unwrap();
-                    let mut non_jira_links_str = links_str.to_string(); // Prepare a string to filter out Jira links
-
-                    for cap in jira_regex.captures_iter(links_str) {
-                        if let (Some(full_match), Some(key_match)) = (cap.get(0), cap.get(2)) {
-                            // Cap 0 is the full match
-                            let summary = cap.get(3).map(|m| m.as_str().to_string());
-                            entry.jira_tickets.push(JiraTicketRef {
-                                key: key_match.as_str().to_string(),
-                                summary,
-                            });
-                            // Remove the identified Jira link from non_jira_links_str
-                            non_jira_links_str =
-                                non_jira_links_str.replace(full_match.as_str(), "");
-                        }
-                    }
+    if !file_path.exists() {
+        return Ok(());
+    }
 
-                    // Parse les autres liens markdown [text](url) from the filtered string
-                    let link_regex = regex::Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap();
-                    for cap in link_regex.captures_iter(&non_jira_links_str) {
-                        // Use the filtered string
-                        if let (Some(text), Some(url)) = (cap.get(1), cap.get(2)) {
-                            entry.links.push(Link {
-                                text: text.as_str().to_string(),
-                                url: url.as_str().to_string(),
-                            });
-                        }
-                    }
-                }
+    let content = fs::read_to_string(&file_path)?;
+    let mut document = parse_journal_document(&content);
+    let mut changed = false;
+    for entry in &mut document.entries {
+        if let Some(refs) = worklogs.get(&entry.id) {
+            if &entry.worklogs != refs {
+                entry.worklogs = refs.clone();
+                changed = true;
             }
-
-            i += 1;
         }
-
-        entries.push(entry);
     }
 
-    entries
-}
-
-pub fn generate_markdown_entry(entry: &ParsedJournalEntry) -> String {
-    let timestamp = if entry.timestamp.contains('/') {
-        entry.timestamp.clone()
-    } else {
-        // Utiliser le timestamp tel quel
-        entry.timestamp.clone()
-    };
-
-    let mut content = format!("## {}\n", timestamp);
-    content.push_str(&format!("**Projet**: {}  \n", entry.project));
-
-    if !entry.time_range.is_empty() {
-        content.push_str(&format!("**Plage horaire**: {}  \n", entry.time_range));
-    }
-
-    content.push_str(&format!("**Type d'activité**: {}  \n", entry.entry_type));
-    content.push_str(&format!("**Description**: {}  \n", entry.description));
-    content.push_str(&format!("**Durée**: {} minutes  \n", entry.duration));
-
-    if !entry.results.is_empty() {
-        content.push_str(&format!("**Résultats**: {}  \n", entry.results));
-    }
-
-    if !entry.blockers.is_empty() {
-        content.push_str(&format!("**Blocages**: {}  \n", entry.blockers));
+    if changed {
+        write_journal_file(&file_path, &render_journal_document(&document, None))?;
     }
+    Ok(())
+}
 
-    // Générer les liens Jira
-    let mut jira_markdown_links = Vec::new();
-    if !entry.jira_tickets.is_empty() {
-        let base_url = std::env::var("JIRA_BASE_URL")
-            .unwrap_or_else(|_| "https://votre-instance.atlassian.net".to_string());
-        for ticket in &entry.jira_tickets {
-            let display_text = if let Some(summary) = &ticket.summary {
-                format!("{} - {}", ticket.key, summary)
-            } else {
-                ticket.key.clone()
-            };
-            jira_markdown_links.push(format!(
-                "[{}]({}/browse/{})",
-                display_text, base_url, ticket.key
-            ));
-        }
-    }
+fn entry_not_found(entry_id: &str) -> anyhow::Error {
+    AppError::conflict(format!(
+        "Entrée introuvable ({}) : elle a été supprimée ou modifiée depuis le chargement",
+        entry_id
+    ))
+    .into()
+}
 
-    // Générer les liens normaux
-    let mut regular_markdown_links = Vec::new();
-    if !entry.links.is_empty() {
-        for link in entry
-            .links
+// Remplace l'entrée identifiée par `entry_id` et renvoie le nouveau contenu du fichier
+fn replace_entry_in_content(
+    content: &str,
+    entry_id: &str,
+    updated_entry: &ParsedJournalEntry,
+    jira_base_url: Option<&str>,
+) -> Result<String> {
+    let mut document = parse_journal_document(content);
+    let entry = document
+        .entries
+        .iter_mut()
+        .find(|entry| entry.id == entry_id)
+        .ok_or_else(|| entry_not_found(entry_id))?;
+
+    // Les tickets déjà liés gardent l'adresse de leur lien
+    let mut jira_tickets = updated_entry.jira_tickets.clone();
+    for ticket in jira_tickets
+        .iter_mut()
+        .filter(|ticket| ticket.url.is_none())
+    {
+        ticket.url = entry
+            .jira_tickets
             .iter()
-            .filter(|link| !link.text.is_empty() && !link.url.is_empty())
-        {
-            regular_markdown_links.push(format!("[{}]({})", link.text, link.url));
-        }
+            .find(|previous| previous.key == ticket.key)
+            .and_then(|previous| previous.url.clone());
     }
 
-    // Combiner tous les liens pour la section "Liens"
-    let mut all_markdown_links = Vec::new();
-    all_markdown_links.extend(jira_markdown_links);
-    all_markdown_links.extend(regular_markdown_links);
-
-    if !all_markdown_links.is_empty() {
-        content.push_str(&format!("**Liens**: {}  \n", all_markdown_links.join(", ")));
-    }
-
-    let tags_str = if entry.tags.is_empty() {
-        "Aucun".to_string()
-    } else {
-        entry
-            .tags
-            .iter()
-            .map(|tag| format!("#{}", tag))
-            .collect::<Vec<_>>()
-            .join(" ")
+    // Garder l'identifiant, le timestamp et les worklogs synchronisés
+    // originaux mais mettre à jour le reste
+    *entry = ParsedJournalEntry {
+        id: entry.id.clone(),
+        timestamp: entry.timestamp.clone(),
+        worklogs: entry.worklogs.clone(),
+        jira_tickets,
+        ..updated_entry.clone()
     };
-    content.push_str(&format!("**Tags**: {}  \n", tags_str));
 
-    if !entry.reflections.is_empty() {
-        content.push_str(&format!("**Réflexions**: {}  \n", entry.reflections));
-    }
+    Ok(render_journal_document(&document, jira_base_url))
+}
 
-    content
+// Supprime l'entrée identifiée par `entry_id` et renvoie le nouveau contenu du fichier
+fn remove_entry_from_content(content: &str, entry_id: &str) -> Result<String> {
+    let mut document = parse_journal_document(content);
+    let position = document
+        .entries
+        .iter()
+        .position(|entry| entry.id == entry_id)
+        .ok_or_else(|| entry_not_found(entry_id))?;
+    document.entries.remove(position);
+
+    Ok(render_journal_document(&document, None))
 }
 
 pub fn update_journal_entry(
+    journal_dir: &Path,
     date: &str,
-    entry_index: usize,
+    entry_id: &str,
     updated_entry: &ParsedJournalEntry,
-) -> Result<bool> {
-    let journal_dir = get_journal_dir()?;
-    let file_path = build_journal_file_path(&journal_dir, date)?;
+    jira_base_url: Option<&str>,
+) -> Result<()> {
+    // Sans durée saisie, la durée est celle de la plage horaire
+    let duration = effective_duration(updated_entry).map_err(|e| {
+        AppError::invalid_field(
+            "duration",
+            format!("{} : « {} »", e, updated_entry.duration),
+        )
+    })?;
+    let updated_entry = &ParsedJournalEntry {
+        duration: EntryDuration::Known(duration),
+        ..updated_entry.clone()
+    };
+
+    let file_path = build_journal_file_path(journal_dir, date)?;
+
+    let lock = journal_file_lock(&file_path);
+    let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
 
     if !file_path.exists() {
-        return Ok(false);
+        return Err(entry_not_found(entry_id));
     }
 
     let content = fs::read_to_string(&file_path)?;
-    let mut entries = parse_journal_entries(&content);
-
-    if entry_index < entries.len() {
-        // Garder le timestamp original mais mettre à jour le reste
-        entries[entry_index] = ParsedJournalEntry {
-            timestamp: entries[entry_index].timestamp.clone(),
-            ..updated_entry.clone()
-        };
-
-        // Régénérer le contenu complet
-        let new_content = entries
-            .iter()
-            .map(|entry| generate_markdown_entry(entry))
-            .collect::<Vec<_>>()
-            .join("\n\n");
-
-        fs::write(&file_path, new_content)?;
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+    let new_content = replace_entry_in_content(&content, entry_id, updated_entry, jira_base_url)?;
+    write_journal_file(&file_path, &new_content)
 }
 
-pub fn delete_journal_entry(date: &str, entry_index: usize) -> Result<bool> {
-    let journal_dir = get_journal_dir()?;
-    let file_path = build_journal_file_path(&journal_dir, date)?;
+pub fn delete_journal_entry(journal_dir: &Path, date: &str, entry_id: &str) -> Result<()> {
+    let file_path = build_journal_file_path(journal_dir, date)?;
+
+    let lock = journal_file_lock(&file_path);
+    let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
 
     if !file_path.exists() {
-        return Ok(false);
+        return Err(entry_not_found(entry_id));
     }
 
     let content = fs::read_to_string(&file_path)?;
-    let mut entries = parse_journal_entries(&content);
+    let new_content = remove_entry_from_content(&content, entry_id)?;
+    write_journal_file(&file_path, &new_content)
+}
 
-    if entry_index < entries.len() {
-        entries.remove(entry_index);
+pub fn restore_journal_backup(journal_dir: &Path, date: &str) -> Result<()> {
+    let file_path = build_journal_file_path(journal_dir, date)?;
 
-        // Régénérer le contenu complet
-        let new_content = if entries.is_empty() {
-            String::new()
-        } else {
-            entries
-                .iter()
-                .map(|entry| generate_markdown_entry(entry))
-                .collect::<Vec<_>>()
-                .join("\n\n")
-        };
-
-        fs::write(&file_path, new_content)?;
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+    let lock = journal_file_lock(&file_path);
+    let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    restore_journal_file(&file_path)
 }
 
-fn format_entry_as_markdown(entry: &JournalEntry) -> String {
-    let now = chrono::Utc::now();
-    let timestamp = now.format("%d/%m/%Y %H:%M").to_string();
+fn format_entry_as_markdown(
+    entry: &JournalEntry,
+    date: &str,
+    timestamp_format: TimestampFormat,
+    jira_base_url: Option<&str>,
+) -> Result<String> {
+    // Sans durée saisie, la durée est celle de la plage horaire
+    let duration = match Duration::parse(&entry.duration) {
+        Err(DurationError::Empty) => TimeRange::parse(&entry.time_range)
+            .map(|range| range.duration())
+            .ok_or(DurationError::Empty),
+        parsed => parsed,
+    }
+    .map_err(|e| {
+        AppError::invalid_field("duration", format!("{} : « {} »", e, entry.duration.trim()))
+    })?;
 
     let parsed_entry = ParsedJournalEntry {
-        timestamp,
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: entry_timestamp(date, timestamp_format),
         project: entry.project.clone(),
         description: entry.description.clone(),
-        duration: entry.duration.clone(),
+        duration: EntryDuration::Known(duration),
         tags: entry.tags.clone(),
         time_range: entry.time_range.clone(),
         entry_type: entry.entry_type.clone(),
@@ -505,16 +484,219 @@ fn format_entry_as_markdown(entry: &JournalEntry) -> String {
         links: entry.links.clone(),
         reflections: entry.reflections.clone(),
         jira_tickets: entry.jira_tickets.clone(),
+        worklogs: Vec::new(),
+        notes: String::new(),
+        extra_fields: Vec::new(),
     };
 
-    generate_markdown_entry(&parsed_entry)
+    Ok(generate_markdown_entry(&parsed_entry, jira_base_url))
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct DurationMigration {
+    // Jours dont le fichier a été réécrit (ou le serait, en simulation)
+    pub rewritten_days: Vec<String>,
+    // Durées ambiguës converties dans l'unité choisie
+    pub resolved: Vec<DurationIssue>,
+    // Durées toujours illisibles, à corriger à la main
+    pub unresolved: Vec<DurationIssue>,
+}
+
+// Réécrit toutes les durées du journal sous leur forme canonique. Les valeurs
+// ambiguës de l'ancien format ("5 minutes" comptait pour 5 heures) ne sont
+// converties que si l'unité à retenir est précisée ; sinon elles sont signalées.
+pub fn migrate_journal_durations(
+    journal_dir: &Path,
+    ambiguous_unit: Option<AmbiguousUnit>,
+    dry_run: bool,
+) -> Result<DurationMigration> {
+    let mut migration = DurationMigration::default();
+
+    for file in list_journal_files(journal_dir)? {
+        let lock = journal_file_lock(&file.path);
+        let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let content = fs::read_to_string(&file.path)?;
+        let mut document = parse_journal_document(&content);
+        let mut resolved_any = false;
+
+        for entry in &mut document.entries {
+            let Some(error) = entry.duration.error() else {
+                continue;
+            };
+            let issue = DurationIssue {
+                date: file.date.clone(),
+                entry_id: entry.id.clone(),
+                timestamp: entry.timestamp.clone(),
+                value: entry.duration.to_string(),
+                reason: error.to_string(),
+            };
+
+            match ambiguous_unit.and_then(|unit| entry.duration.resolve_ambiguous(unit)) {
+                Some(duration) => {
+                    entry.duration = EntryDuration::Known(duration);
+                    migration.resolved.push(issue);
+                    resolved_any = true;
+                }
+                None => migration.unresolved.push(issue),
+            }
+        }
+
+        if resolved_any || has_non_canonical_durations(&content) {
+            if !dry_run {
+                write_journal_file(&file.path, &render_journal_document(&document, None))?;
+            }
+            migration.rewritten_days.push(file.date);
+        }
+    }
+
+    migration.rewritten_days.sort();
+    Ok(migration)
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct JiraLinkRewrite {
+    // Jours dont le fichier a été réécrit (ou le serait, en simulation)
+    pub rewritten_days: Vec<String>,
+    pub links: usize,
+}
+
+// Fait pointer les liens Jira de tout le journal vers `base_url`, après un
+// changement d'instance. Seuls les liens sont modifiés, le reste des fichiers
+// est conservé tel quel.
+pub fn rewrite_journal_jira_links(
+    journal_dir: &Path,
+    base_url: Option<&str>,
+    previous_base_url: Option<&str>,
+    dry_run: bool,
+) -> Result<JiraLinkRewrite> {
+    let mut rewrite = JiraLinkRewrite::default();
+
+    for file in list_journal_files(journal_dir)? {
+        let lock = journal_file_lock(&file.path);
+        let _guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let content = fs::read_to_string(&file.path)?;
+        let (rewritten, links) = rewrite_jira_links(&content, base_url, previous_base_url);
+        if links == 0 {
+            continue;
+        }
+        if !dry_run {
+            write_journal_file(&file.path, &rewritten)?;
+        }
+        rewrite.links += links;
+        rewrite.rewritten_days.push(file.date);
+    }
+
+    rewrite.rewritten_days.sort();
+    Ok(rewrite)
+}
+
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct EntryRewrite {
+    // Jours dont le fichier a été réécrit (ou le serait, en simulation)
+    pub rewritten_days: Vec<String>,
+    pub entries: usize,
+}
+
+// Applique `rewrite` à toutes les entrées du journal ; il renvoie `true` s'il
+// a modifié l'entrée. Tous les fichiers sont verrouillés pendant l'opération
+// et réécrits ensemble : si l'un échoue, ceux déjà écrits retrouvent leur
+// contenu d'origine.
+pub fn rewrite_journal_entries(
+    journal_dir: &Path,
+    dry_run: bool,
+    mut rewrite: impl FnMut(&mut ParsedJournalEntry) -> bool,
+) -> Result<EntryRewrite> {
+    let mut files = list_journal_files(journal_dir)?;
+    // Toujours le même ordre de verrouillage, pour ne jamais s'interbloquer
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let locks: Vec<_> = files
+        .iter()
+        .map(|file| journal_file_lock(&file.path))
+        .collect();
+    let _guards: Vec<_> = locks
+        .iter()
+        .map(|lock| lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+        .collect();
+
+    let mut result = EntryRewrite::default();
+    let mut pending = Vec::new();
+    for file in &files {
+        let content = fs::read_to_string(&file.path)?;
+        let mut document = parse_journal_document(&content);
+        let changed = document
+            .entries
+            .iter_mut()
+            .filter_map(|entry| rewrite(entry).then_some(()))
+            .count();
+        if changed == 0 {
+            continue;
+        }
+        result.entries += changed;
+        result.rewritten_days.push(file.date.clone());
+        pending.push((file, content, render_journal_document(&document, None)));
+    }
+
+    if !dry_run {
+        for (written, (file, _, rewritten)) in pending.iter().enumerate() {
+            if let Err(e) = write_journal_file(&file.path, rewritten) {
+                for (file, original, _) in &pending[..written] {
+                    if let Err(restore_error) =
+                        write_file_atomically(&file.path, original.as_bytes())
+                    {
+                        tracing::error!(
+                            "journal file {} not restored: {}",
+                            file.path.display(),
+                            restore_error
+                        );
+                    }
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    result.rewritten_days.sort();
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{build_journal_file_path, collect_journal_dates_in_dir};
+    use super::{
+        backup_file_path, build_journal_file_path, collect_journal_dates_in_dir,
+        migrate_journal_durations, remove_entry_from_content, replace_entry_in_content,
+        restore_journal_file, rewrite_journal_entries, rewrite_journal_jira_links,
+        write_journal_file,
+    };
+    use crate::duration::{AmbiguousUnit, Duration, EntryDuration};
+    use crate::markdown::{
+        generate_markdown_entry, parse_journal_entries, JiraTicketRef, ParsedJournalEntry,
+        ENTRY_SEPARATOR,
+    };
     use std::fs;
 
+    fn sample_entry(id: &str, description: &str) -> ParsedJournalEntry {
+        ParsedJournalEntry {
+            id: id.to_string(),
+            timestamp: "06/04/2026 09:00".to_string(),
+            project: "Mandate".to_string(),
+            description: description.to_string(),
+            duration: EntryDuration::Known(Duration::from_minutes(30)),
+            tags: vec!["bug".to_string()],
+            time_range: String::new(),
+            entry_type: "développement".to_string(),
+            results: String::new(),
+            blockers: String::new(),
+            links: Vec::new(),
+            reflections: String::new(),
+            jira_tickets: Vec::new(),
+            worklogs: Vec::new(),
+            notes: String::new(),
+            extra_fields: Vec::new(),
+        }
+    }
+
     fn make_temp_dir() -> std::path::PathBuf {
         let dir = std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
         fs::create_dir_all(&dir).unwrap();
@@ -559,4 +741,285 @@ mod tests {
 
         fs::remove_dir_all(root).unwrap();
     }
+
+    #[test]
+    fn writes_and_reads_back_entry_id() {
+        let markdown = generate_markdown_entry(&sample_entry("abc-123", "Export"), None);
+        let entries = parse_journal_entries(&markdown);
+
+        assert!(markdown.contains("<!-- id: abc-123 -->"));
+        assert_eq!(entries[0].id, "abc-123");
+    }
+
+    #[test]
+    fn legacy_entries_get_stable_ids_when_entries_are_appended() {
+        let legacy = "## 06/04/2026 09:00\n**Projet**: Mandate  \n**Description**: Export  \n";
+        let appended = format!(
+            "{}\n\n---\n\n## 06/04/2026 10:00\n**Projet**: Claims  \n",
+            legacy
+        );
+
+        let before = parse_journal_entries(legacy);
+        let after = parse_journal_entries(&appended);
+
+        assert!(!before[0].id.is_empty());
+        assert_eq!(before[0].id, after[0].id);
+        assert_ne!(after[0].id, after[1].id);
+    }
+
+    #[test]
+    fn identical_legacy_entries_get_distinct_ids() {
+        let legacy = "## 06/04/2026 09:00\n**Projet**: Mandate  \n**Description**: Export  \n";
+        let content = format!("{}\n\n---\n\n{}", legacy, legacy);
+
+        let entries = parse_journal_entries(&content);
+        assert_eq!(entries[0].id, parse_journal_entries(legacy)[0].id);
+        assert_ne!(entries[0].id, entries[1].id);
+
+        let updated = replace_entry_in_content(
+            &content,
+            &entries[1].id,
+            &sample_entry("ignored", "Modifié"),
+            None,
+        )
+        .unwrap();
+        let updated_entries = parse_journal_entries(&updated);
+        assert_eq!(updated_entries[0].description, "Export");
+        assert_eq!(updated_entries[1].description, "Modifié");
+
+        let remaining =
+            parse_journal_entries(&remove_entry_from_content(&content, &entries[1].id).unwrap());
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, entries[0].id);
+    }
+
+    #[test]
+    fn updates_and_deletes_target_entries_by_id() {
+        let content = [
+            generate_markdown_entry(&sample_entry("first", "Premier"), None),
+            generate_markdown_entry(&sample_entry("second", "Second"), None),
+        ]
+        .join(ENTRY_SEPARATOR);
+
+        let updated = replace_entry_in_content(
+            &content,
+            "second",
+            &sample_entry("ignored", "Modifié"),
+            None,
+        )
+        .unwrap();
+        let entries = parse_journal_entries(&updated);
+        assert_eq!(entries[0].description, "Premier");
+        assert_eq!(entries[1].id, "second");
+        assert_eq!(entries[1].description, "Modifié");
+
+        let remaining =
+            parse_journal_entries(&remove_entry_from_content(&updated, "first").unwrap());
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "second");
+    }
+
+    #[test]
+    fn keeps_ticket_links_when_updating_an_entry() {
+        let ticket = |key: &str, url: Option<&str>| JiraTicketRef {
+            key: key.to_string(),
+            summary: None,
+            url: url.map(str::to_string),
+        };
+        let mut entry = sample_entry("first", "Premier");
+        entry.jira_tickets = vec![ticket(
+            "OPS-1",
+            Some("https://old.atlassian.net/browse/OPS-1"),
+        )];
+        let content = generate_markdown_entry(&entry, None);
+
+        // Le formulaire renvoie les tickets sans leur adresse
+        entry.jira_tickets = vec![ticket("OPS-1", None), ticket("OPS-2", None)];
+        let updated =
+            replace_entry_in_content(&content, "first", &entry, Some("https://new.atlassian.net"))
+                .unwrap();
+        assert!(updated.contains("[OPS-1](https://old.atlassian.net/browse/OPS-1)"));
+        assert!(updated.contains("[OPS-2](https://new.atlassian.net/browse/OPS-2)"));
+    }
+
+    #[test]
+    fn rejects_unknown_entry_ids() {
+        let content = generate_markdown_entry(&sample_entry("first", "Premier"), None);
+
+        assert!(
+            replace_entry_in_content(&content, "missing", &sample_entry("x", "y"), None).is_err()
+        );
+        assert!(remove_entry_from_content(&content, "missing").is_err());
+    }
+
+    #[test]
+    fn keeps_previous_version_as_backup_and_restores_it() {
+        let root = make_temp_dir();
+        let file_path = root.join("2026-04-06.md");
+
+        write_journal_file(&file_path, "version 1").unwrap();
+        assert!(!backup_file_path(&file_path).exists());
+
+        write_journal_file(&file_path, "version 2").unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "version 2");
+        assert_eq!(
+            fs::read_to_string(backup_file_path(&file_path)).unwrap(),
+            "version 1"
+        );
+
+        restore_journal_file(&file_path).unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "version 1");
+        assert_eq!(
+            fs::read_to_string(backup_file_path(&file_path)).unwrap(),
+            "version 2"
+        );
+
+        let leftovers: Vec<_> = fs::read_dir(&root)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn refuses_to_restore_without_backup() {
+        let root = make_temp_dir();
+
+        assert!(restore_journal_file(&root.join("2026-04-06.md")).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn migrates_legacy_durations() {
+        let root = make_temp_dir();
+        let file_path = build_journal_file_path(&root, "2026-04-06").unwrap();
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(
+            &file_path,
+            "## 09:00\n**Durée**: 45 minutes  \n\n---\n\n## 10:00\n**Durée**: 2 minutes  \n",
+        )
+        .unwrap();
+
+        let preview = migrate_journal_durations(&root, None, true).unwrap();
+        assert_eq!(preview.rewritten_days, vec!["2026-04-06"]);
+        assert_eq!(preview.unresolved.len(), 1);
+        assert_eq!(preview.unresolved[0].value, "2 minutes");
+        assert!(fs::read_to_string(&file_path)
+            .unwrap()
+            .contains("45 minutes"));
+
+        let migration =
+            migrate_journal_durations(&root, Some(AmbiguousUnit::Hours), false).unwrap();
+        assert_eq!(migration.resolved.len(), 1);
+        assert!(migration.unresolved.is_empty());
+
+        let entries = parse_journal_entries(&fs::read_to_string(&file_path).unwrap());
+        assert_eq!(
+            entries[0].duration,
+            EntryDuration::Known(Duration::from_minutes(45))
+        );
+        assert_eq!(
+            entries[1].duration,
+            EntryDuration::Known(Duration::from_minutes(120))
+        );
+
+        let again = migrate_journal_durations(&root, None, false).unwrap();
+        assert!(again.rewritten_days.is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rewrites_jira_links_across_the_journal() {
+        let root = make_temp_dir();
+        let day = |date: &str, content: &str| {
+            let file_path = build_journal_file_path(&root, date).unwrap();
+            fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            fs::write(&file_path, content).unwrap();
+            file_path
+        };
+        let linked = day(
+            "2026-04-06",
+            "## 09:00\n**Liens**: [OPS-1](https://votre-instance.atlassian.net/browse/OPS-1)  \n",
+        );
+        day("2026-04-07", "## 09:00\n**Description**: Rien à lier  \n");
+
+        let preview =
+            rewrite_journal_jira_links(&root, Some("https://acme.atlassian.net"), None, true)
+                .unwrap();
+        assert_eq!(preview.rewritten_days, vec!["2026-04-06"]);
+        assert_eq!(preview.links, 1);
+        assert!(fs::read_to_string(&linked)
+            .unwrap()
+            .contains("votre-instance"));
+
+        rewrite_journal_jira_links(&root, Some("https://acme.atlassian.net"), None, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(&linked).unwrap(),
+            "## 09:00\n**Liens**: [OPS-1](https://acme.atlassian.net/browse/OPS-1)  \n"
+        );
+        assert!(backup_file_path(&linked).exists());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rewrites_entries_across_the_journal() {
+        let root = make_temp_dir();
+        let write_day = |date: &str, entries: &[ParsedJournalEntry]| {
+            let file_path = build_journal_file_path(&root, date).unwrap();
+            fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            let content: Vec<String> = entries
+                .iter()
+                .map(|entry| generate_markdown_entry(entry, None))
+                .collect();
+            fs::write(&file_path, content.join(ENTRY_SEPARATOR)).unwrap();
+            file_path
+        };
+        let mut other = sample_entry("b", "Autre projet");
+        other.project = "Claims".to_string();
+        let monday = write_day(
+            "2026-04-06",
+            &[sample_entry("a", "Première"), other.clone()],
+        );
+        let tuesday = write_day("2026-04-07", &[sample_entry("c", "Seconde")]);
+        write_day("2026-04-08", &[other]);
+        let rename = |entry: &mut ParsedJournalEntry| {
+            if entry.project != "Mandate" {
+                return false;
+            }
+            entry.project = "Mandats".to_string();
+            true
+        };
+
+        let preview = rewrite_journal_entries(&root, true, rename).unwrap();
+        assert_eq!(preview.rewritten_days, vec!["2026-04-06", "2026-04-07"]);
+        assert_eq!(preview.entries, 2);
+        assert!(fs::read_to_string(&monday).unwrap().contains("Mandate"));
+
+        assert_eq!(
+            rewrite_journal_entries(&root, false, rename).unwrap(),
+            preview
+        );
+        for file in [&monday, &tuesday] {
+            let entries = parse_journal_entries(&fs::read_to_string(file).unwrap());
+            assert!(entries.iter().all(|entry| entry.project != "Mandate"));
+        }
+        assert_eq!(
+            parse_journal_entries(&fs::read_to_string(&monday).unwrap())[1].project,
+            "Claims"
+        );
+        assert_eq!(
+            rewrite_journal_entries(&root, false, rename)
+                .unwrap()
+                .entries,
+            0
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
 }
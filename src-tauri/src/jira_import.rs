@@ -0,0 +1,263 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone, Timelike};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::duration::Duration;
+use crate::file_manager::JournalEntry;
+use crate::jira::{JiraActivity, JiraActivityKind};
+use crate::markdown::{JiraTicketRef, ParsedJournalEntry};
+
+const DRAFT_ENTRY_TYPE: &str = "développement";
+
+// Entrée proposée à partir de l'activité Jira d'une journée sur un ticket. Elle
+// n'est écrite dans le journal que si l'utilisateur l'accepte.
+#[derive(Debug, Serialize)]
+pub struct DraftEntry {
+    pub entry: JournalEntry,
+    pub activities: Vec<JiraActivity>,
+    // Sans worklog, la durée est à saisir avant l'ajout au journal
+    pub needs_duration: bool,
+}
+
+// Début (inclus) et fin (exclue) de la période, en heure locale
+pub fn local_period(
+    start_date: &str,
+    end_date: &str,
+) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+    let midnight = |day: NaiveDate| {
+        Local
+            .from_local_datetime(&day.and_hms_opt(0, 0, 0)?)
+            .earliest()
+            .map(|at| at.fixed_offset())
+    };
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d").ok()?;
+    let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d").ok()?;
+    Some((midnight(start)?, midnight(end.succ_opt()?)?))
+}
+
+// Identifiants des worklogs déjà présents dans le journal : le temps envoyé
+// depuis une entrée n'est pas reproposé
+pub fn synced_worklog_ids(days: &[(String, Vec<ParsedJournalEntry>)]) -> HashSet<String> {
+    days.iter()
+        .flat_map(|(_, entries)| entries)
+        .flat_map(|entry| &entry.worklogs)
+        .map(|worklog| worklog.worklog_id.clone())
+        .collect()
+}
+
+fn format_time(at: &DateTime<Local>) -> String {
+    format!("{:02}:{:02}", at.hour(), at.minute())
+}
+
+// Regroupe l'activité par journée locale et par ticket. Le projet est celui
+// associé à la clé du projet Jira dans `project_map`, à défaut la clé elle-même.
+pub fn draft_entries(
+    activities: Vec<JiraActivity>,
+    project_map: &BTreeMap<String, String>,
+    synced_worklogs: &HashSet<String>,
+) -> Vec<DraftEntry> {
+    let mut groups: BTreeMap<(String, String), Vec<JiraActivity>> = BTreeMap::new();
+    for activity in activities {
+        if activity.kind == JiraActivityKind::Worklog && synced_worklogs.contains(&activity.id) {
+            continue;
+        }
+        let day = activity
+            .at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d")
+            .to_string();
+        groups
+            .entry((day, activity.issue_key.clone()))
+            .or_default()
+            .push(activity);
+    }
+
+    let mut drafts: Vec<DraftEntry> = groups
+        .into_iter()
+        .map(|((date, issue_key), mut activities)| {
+            activities.sort_by_key(|activity| activity.at);
+            let first = &activities[0];
+
+            let worklogs: Vec<&JiraActivity> = activities
+                .iter()
+                .filter(|activity| activity.kind == JiraActivityKind::Worklog)
+                .collect();
+            let minutes: u32 = worklogs.iter().filter_map(|worklog| worklog.minutes).sum();
+
+            // Une plage horaire n'a de sens que pour un worklog unique
+            let time_range = match worklogs.as_slice() {
+                [worklog] => {
+                    let start = worklog.at.with_timezone(&Local);
+                    let end = start + chrono::Duration::minutes(minutes as i64);
+                    if minutes > 0 && end.date_naive() == start.date_naive() {
+                        format!("{}-{}", format_time(&start), format_time(&end))
+                    } else {
+                        String::new()
+                    }
+                }
+                _ => String::new(),
+            };
+
+            let mut description = vec![first.summary.clone()];
+            let mut results = Vec::new();
+            for activity in &activities {
+                let text = activity.text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                match activity.kind {
+                    JiraActivityKind::Transition => results.push(format!("Statut : {}", text)),
+                    JiraActivityKind::Worklog | JiraActivityKind::Comment => {
+                        description.push(format!("- {}", text.replace('\n', " ")))
+                    }
+                }
+            }
+
+            let entry = JournalEntry {
+                date,
+                time_range,
+                project: project_map
+                    .get(&first.project_key)
+                    .cloned()
+                    .unwrap_or_else(|| first.project_key.clone()),
+                entry_type: DRAFT_ENTRY_TYPE.to_string(),
+                description: description.join("\n"),
+                duration: match minutes {
+                    0 => String::new(),
+                    minutes => Duration::from_minutes(minutes).to_string(),
+                },
+                results: results.join("\n"),
+                blockers: String::new(),
+                links: Vec::new(),
+                tags: first.labels.clone(),
+                reflections: String::new(),
+                jira_tickets: vec![JiraTicketRef {
+                    key: issue_key,
+                    summary: Some(first.summary.clone()),
+                    url: None,
+                }],
+            };
+            DraftEntry {
+                needs_duration: entry.duration.is_empty(),
+                entry,
+                activities,
+            }
+        })
+        .collect();
+
+    drafts.sort_by_key(|draft| draft.activities[0].at);
+    drafts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::draft_entries;
+    use crate::jira::{JiraActivity, JiraActivityKind};
+    use chrono::{DateTime, Local, TimeZone};
+    use std::collections::{BTreeMap, HashSet};
+
+    fn activity(
+        id: &str,
+        kind: JiraActivityKind,
+        hour: u32,
+        minutes: Option<u32>,
+        text: &str,
+    ) -> JiraActivity {
+        JiraActivity {
+            id: id.to_string(),
+            issue_key: "CSE-12".to_string(),
+            summary: "Export DOCX".to_string(),
+            project_key: "CSE".to_string(),
+            labels: vec!["export".to_string()],
+            kind,
+            at: local(hour),
+            minutes,
+            text: text.to_string(),
+        }
+    }
+
+    fn local(hour: u32) -> DateTime<chrono::FixedOffset> {
+        Local
+            .with_ymd_and_hms(2026, 4, 6, hour, 0, 0)
+            .unwrap()
+            .fixed_offset()
+    }
+
+    #[test]
+    fn drafts_one_entry_per_day_and_ticket() {
+        let activities = vec![
+            activity("1", JiraActivityKind::Worklog, 9, Some(90), "Revue"),
+            activity(
+                "10",
+                JiraActivityKind::Transition,
+                11,
+                None,
+                "À faire → En cours",
+            ),
+            activity(
+                "20",
+                JiraActivityKind::Comment,
+                12,
+                None,
+                "Prêt pour la recette",
+            ),
+        ];
+        let project_map = BTreeMap::from([("CSE".to_string(), "Mandate".to_string())]);
+
+        let drafts = draft_entries(activities, &project_map, &HashSet::new());
+
+        assert_eq!(drafts.len(), 1);
+        let entry = &drafts[0].entry;
+        assert_eq!(entry.date, "2026-04-06");
+        assert_eq!(entry.project, "Mandate");
+        assert_eq!(entry.duration, "1h30");
+        assert_eq!(entry.time_range, "09:00-10:30");
+        assert_eq!(
+            entry.description,
+            "Export DOCX\n- Revue\n- Prêt pour la recette"
+        );
+        assert_eq!(entry.results, "Statut : À faire → En cours");
+        assert_eq!(entry.tags, vec!["export"]);
+        assert_eq!(entry.jira_tickets[0].key, "CSE-12");
+    }
+
+    #[test]
+    fn asks_for_a_duration_without_worklogs() {
+        let activities = vec![
+            activity("1", JiraActivityKind::Worklog, 9, Some(90), ""),
+            activity("20", JiraActivityKind::Comment, 12, None, "Relu"),
+        ];
+
+        let drafts = draft_entries(
+            activities,
+            &BTreeMap::new(),
+            &HashSet::from(["1".to_string()]),
+        );
+
+        assert_eq!(drafts.len(), 1);
+        assert!(drafts[0].needs_duration);
+        assert!(drafts[0].entry.duration.is_empty());
+        assert!(drafts[0].entry.time_range.is_empty());
+        assert!(
+            !draft_entries(
+                vec![activity("2", JiraActivityKind::Worklog, 9, Some(30), "")],
+                &BTreeMap::new(),
+                &HashSet::new(),
+            )[0]
+            .needs_duration
+        );
+    }
+
+    #[test]
+    fn leaves_out_worklogs_already_in_the_journal() {
+        let activities = vec![activity("1", JiraActivityKind::Worklog, 9, Some(90), "")];
+
+        let drafts = draft_entries(
+            activities,
+            &BTreeMap::new(),
+            &HashSet::from(["1".to_string()]),
+        );
+
+        assert!(drafts.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use regex::Regex;
+
+use crate::error::AppError;
+use crate::markdown::JiraTicketRef;
+
+// Champ de la configuration Jira du profil portant les motifs
+pub const KEY_PATTERNS_FIELD: &str = "key_patterns";
+
+// Repère dans le texte d'une entrée les clés de ticket ("CLAIMS-412") des
+// projets configurés. Chaque motif décrit une clé de projet : "CLAIMS", ou
+// une expression comme "OPS|SUP" ou "INFRA[0-9]?". Sans motif, rien n'est
+// détecté : une détection de toute suite "ABC-123" confondrait les tickets
+// avec "UTF-8" ou "SHA-256".
+pub struct KeyDetector {
+    regex: Option<Regex>,
+}
+
+impl KeyDetector {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<Self, AppError> {
+        let mut alternatives = Vec::new();
+        for pattern in patterns
+            .into_iter()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+        {
+            Regex::new(pattern).map_err(|e| {
+                AppError::invalid_field(
+                    KEY_PATTERNS_FIELD,
+                    format!("Motif de clé Jira invalide ({}) : {}", pattern, e),
+                )
+            })?;
+            alternatives.push(format!("(?:{})", pattern));
+        }
+        if alternatives.is_empty() {
+            return Ok(Self { regex: None });
+        }
+
+        let regex = Regex::new(&format!(
+            r"\b((?:{})-[1-9][0-9]*)\b",
+            alternatives.join("|")
+        ))
+        .map_err(|e| AppError::invalid_field(KEY_PATTERNS_FIELD, e.to_string()))?;
+        Ok(Self { regex: Some(regex) })
+    }
+
+    // Clés citées dans les textes, sans doublon, dans l'ordre d'apparition
+    pub fn detect(&self, texts: &[&str]) -> Vec<String> {
+        let Some(regex) = &self.regex else {
+            return Vec::new();
+        };
+        let mut keys: Vec<String> = Vec::new();
+        for text in texts {
+            for found in regex.captures_iter(text).filter_map(|cap| cap.get(1)) {
+                if !keys.iter().any(|key| key == found.as_str()) {
+                    keys.push(found.as_str().to_string());
+                }
+            }
+        }
+        keys
+    }
+
+    // Clés citées qui ne sont pas encore parmi les tickets de l'entrée
+    pub fn new_keys(&self, texts: &[&str], tickets: &[JiraTicketRef]) -> Vec<String> {
+        self.detect(texts)
+            .into_iter()
+            .filter(|key| {
+                !tickets
+                    .iter()
+                    .any(|ticket| ticket.key.eq_ignore_ascii_case(key))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyDetector;
+    use crate::error::ErrorCode;
+    use crate::markdown::JiraTicketRef;
+
+    #[test]
+    fn detects_keys_of_configured_projects_only() {
+        let detector = KeyDetector::new(["CLAIMS", "OPS|SUP"]).unwrap();
+
+        assert_eq!(
+            detector.detect(&[
+                "fixed CLAIMS-412 today, see also SUP-7.",
+                "UTF-8 export, OPSX-1 and OPS-12a are not tickets; CLAIMS-412 again",
+                "(OPS-3)",
+            ]),
+            vec!["CLAIMS-412", "SUP-7", "OPS-3"]
+        );
+        assert!(KeyDetector::new(Vec::<&str>::new())
+            .unwrap()
+            .detect(&["CLAIMS-412"])
+            .is_empty());
+    }
+
+    #[test]
+    fn skips_tickets_already_linked() {
+        let detector = KeyDetector::new(["CLAIMS"]).unwrap();
+        let linked = vec![JiraTicketRef {
+            key: "CLAIMS-412".to_string(),
+            summary: None,
+            url: None,
+        }];
+
+        assert_eq!(
+            detector.new_keys(&["CLAIMS-412 puis CLAIMS-413"], &linked),
+            vec!["CLAIMS-413"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let error = KeyDetector::new(["CLAIMS", "OPS("]).err().unwrap();
+
+        assert_eq!(error.code, ErrorCode::Validation);
+        assert_eq!(error.details[0].field, "key_patterns");
+    }
+}
@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+
+use crate::jira::{CommentSection, JiraClient, JiraComment};
+use crate::markdown::ParsedJournalEntry;
+
+// Commentaire tiré des résultats et des blocages d'une entrée ; None si les
+// deux sont vides
+pub fn entry_comment(entry: &ParsedJournalEntry) -> Option<JiraComment> {
+    let comment = JiraComment {
+        sections: vec![
+            CommentSection {
+                title: "Résultats".to_string(),
+                text: entry.results.clone(),
+            },
+            CommentSection {
+                title: "Blocages".to_string(),
+                text: entry.blockers.clone(),
+            },
+        ],
+    };
+    (!comment.is_empty()).then_some(comment)
+}
+
+// Mise à jour demandée sur un ticket lié, à l'enregistrement d'une entrée
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EntryIssueUpdate {
+    pub issue_key: String,
+    // Publier les résultats et blocages de l'entrée en commentaire
+    #[serde(default)]
+    pub comment: bool,
+    #[serde(default)]
+    pub transition_id: Option<String>,
+}
+
+// Ce qui a été fait sur un ticket. En cas d'échec, `error` l'explique et les
+// étapes déjà réussies restent renseignées : un commentaire publié avant
+// l'échec d'une transition n'est pas republié en relançant la transition seule.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct EntryIssueResult {
+    pub issue_key: String,
+    pub comment_id: Option<String>,
+    pub transitioned: bool,
+    pub error: Option<String>,
+}
+
+// Commente puis fait avancer un ticket lié à l'entrée. Le commentaire passe
+// en premier : certains workflows n'acceptent plus de commentaire une fois
+// le ticket terminé.
+pub async fn apply_entry_update(
+    client: &JiraClient,
+    entry: &ParsedJournalEntry,
+    update: &EntryIssueUpdate,
+) -> EntryIssueResult {
+    let issue_key = update.issue_key.trim();
+    let mut result = EntryIssueResult {
+        issue_key: issue_key.to_string(),
+        ..EntryIssueResult::default()
+    };
+    if !entry
+        .jira_tickets
+        .iter()
+        .any(|ticket| ticket.key.eq_ignore_ascii_case(issue_key))
+    {
+        result.error = Some(format!(
+            "Le ticket {} n'est pas lié à cette entrée",
+            issue_key
+        ));
+        return result;
+    }
+
+    if update.comment {
+        if let Some(comment) = entry_comment(entry) {
+            match client.add_comment(issue_key, &comment).await {
+                Ok(comment_id) => result.comment_id = Some(comment_id),
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            }
+        }
+    }
+    if let Some(transition_id) = update
+        .transition_id
+        .as_deref()
+        .filter(|id| !id.trim().is_empty())
+    {
+        match client.transition_issue(issue_key, transition_id).await {
+            Ok(()) => result.transitioned = true,
+            Err(e) => result.error = Some(e.to_string()),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_entry_update, entry_comment, EntryIssueUpdate};
+    use crate::jira::{JiraAuth, JiraClient, JiraDeployment};
+    use crate::markdown::{JiraTicketRef, ParsedJournalEntry};
+    use mockito::Matcher;
+
+    fn entry(results: &str, blockers: &str) -> ParsedJournalEntry {
+        ParsedJournalEntry {
+            id: "entry-1".to_string(),
+            results: results.to_string(),
+            blockers: blockers.to_string(),
+            jira_tickets: vec![JiraTicketRef {
+                key: "OPS-1".to_string(),
+                summary: None,
+                url: None,
+            }],
+            ..ParsedJournalEntry::default()
+        }
+    }
+
+    #[test]
+    fn leaves_out_empty_comments() {
+        assert!(entry_comment(&entry("  ", "")).is_none());
+        assert_eq!(
+            entry_comment(&entry("", "Accès VPN"))
+                .unwrap()
+                .sections
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn comments_then_transitions_a_linked_ticket() {
+        let mut server = mockito::Server::new_async().await;
+        let comment = server
+            .mock("POST", "/rest/api/3/issue/OPS-1/comment")
+            .match_body(Matcher::Json(serde_json::json!({
+                "body": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [
+                        { "type": "heading", "attrs": { "level": 3 }, "content": [{ "type": "text", "text": "Résultats" }] },
+                        { "type": "paragraph", "content": [{ "type": "text", "text": "Export livré" }] },
+                        { "type": "bulletList", "content": [
+                            { "type": "listItem", "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "tests" }] }] },
+                            { "type": "listItem", "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "doc" }] }] }
+                        ] }
+                    ]
+                }
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "id": "10100" }"#)
+            .create_async()
+            .await;
+        let transition = server
+            .mock("POST", "/rest/api/3/issue/OPS-1/transitions")
+            .match_body(Matcher::Json(
+                serde_json::json!({ "transition": { "id": "31" } }),
+            ))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let client = JiraClient::new(server.url(), "moi@acme.com".into(), "token".into())
+            .with_deployment(JiraDeployment::Cloud);
+        let update = EntryIssueUpdate {
+            issue_key: "OPS-1".to_string(),
+            comment: true,
+            transition_id: Some("31".to_string()),
+        };
+        let result =
+            apply_entry_update(&client, &entry("Export livré\n- tests\n- doc", ""), &update).await;
+
+        comment.assert_async().await;
+        transition.assert_async().await;
+        assert_eq!(result.comment_id.as_deref(), Some("10100"));
+        assert!(result.transitioned);
+        assert_eq!(result.error, None);
+    }
+
+    #[tokio::test]
+    async fn keeps_the_posted_comment_when_the_transition_fails() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/rest/api/3/issue/OPS-1/comment")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "id": "10101" }"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/rest/api/3/issue/OPS-1/transitions")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "errorMessages": ["Transition invalide"] }"#)
+            .create_async()
+            .await;
+
+        let client = JiraClient::new(server.url(), "moi@acme.com".into(), "token".into())
+            .with_deployment(JiraDeployment::Cloud);
+        let update = EntryIssueUpdate {
+            issue_key: "OPS-1".to_string(),
+            comment: true,
+            transition_id: Some("99".to_string()),
+        };
+        let result = apply_entry_update(&client, &entry("Fait", ""), &update).await;
+
+        assert_eq!(result.comment_id.as_deref(), Some("10101"));
+        assert!(!result.transitioned);
+        assert!(result.error.unwrap().contains("Transition invalide"));
+    }
+
+    #[tokio::test]
+    async fn refuses_tickets_the_entry_does_not_reference() {
+        let client = JiraClient::with_auth(
+            "https://jira.acme.com".to_string(),
+            Some(JiraAuth::Bearer {
+                token: "pat".into(),
+            }),
+        );
+        let update = EntryIssueUpdate {
+            issue_key: "OPS-2".to_string(),
+            comment: true,
+            ..EntryIssueUpdate::default()
+        };
+
+        let result = apply_entry_update(&client, &entry("Fait", ""), &update).await;
+
+        assert_eq!(result.comment_id, None);
+        assert!(result.error.unwrap().contains("OPS-2"));
+    }
+}
@@ -0,0 +1,642 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::UNIX_EPOCH;
+
+use crate::file_manager::{list_journal_files, FileFingerprint};
+use crate::markdown::{parse_journal_entries, ParsedJournalEntry};
+use crate::report::DayAggregate;
+use crate::search::{
+    fold, fts_query, snippet_for, tokenize, SearchHit, SearchQuery, DEFAULT_SEARCH_LIMIT, FIELDS,
+};
+
+// Schéma de l'index, appliqué dans l'ordre et suivi par `PRAGMA user_version`.
+// L'index n'est qu'un cache des fichiers Markdown : il peut être supprimé à
+// tout moment et sera reconstruit.
+const INDEX_MIGRATIONS: &[&str] = &[
+    r#"
+    CREATE TABLE index_meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+
+    CREATE TABLE journal_days (
+        date TEXT PRIMARY KEY,
+        modified_ns INTEGER,
+        len INTEGER NOT NULL
+    );
+
+    CREATE TABLE entries (
+        id INTEGER PRIMARY KEY,
+        date TEXT NOT NULL REFERENCES journal_days(date) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        entry_id TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        project TEXT NOT NULL,
+        project_key TEXT NOT NULL,
+        entry_type TEXT NOT NULL,
+        entry_type_key TEXT NOT NULL,
+        duration TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX entries_date ON entries(date, position);
+
+    CREATE TABLE entry_tags (
+        entry INTEGER NOT NULL REFERENCES entries(id) ON DELETE CASCADE,
+        tag TEXT NOT NULL,
+        tag_key TEXT NOT NULL
+    );
+    CREATE INDEX entry_tags_key ON entry_tags(tag_key);
+    CREATE INDEX entry_tags_entry ON entry_tags(entry);
+
+    CREATE TABLE entry_jira (
+        entry INTEGER NOT NULL REFERENCES entries(id) ON DELETE CASCADE,
+        key TEXT NOT NULL,
+        summary TEXT
+    );
+    CREATE INDEX entry_jira_key ON entry_jira(key);
+    CREATE INDEX entry_jira_entry ON entry_jira(entry);
+
+    -- Colonnes dans l'ordre de search::FIELDS
+    CREATE VIRTUAL TABLE entries_fts USING fts5(
+        jira, tags, project, description, results, blockers, reflections,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+"#,
+    r#"
+    CREATE TABLE day_aggregates (
+        date TEXT PRIMARY KEY REFERENCES journal_days(date) ON DELETE CASCADE,
+        data TEXT NOT NULL
+    );
+
+    -- Les jours déjà indexés n'ont pas d'agrégat : tout relire
+    DELETE FROM entries_fts;
+    DELETE FROM journal_days;
+"#,
+    r#"
+    ALTER TABLE entries ADD COLUMN duration_minutes INTEGER;
+
+    -- Les durées sont désormais typées : tout relire
+    DELETE FROM entries_fts;
+    DELETE FROM journal_days;
+"#,
+];
+
+const JOURNAL_DIR_KEY: &str = "journal_dir";
+
+// Chaque état de chaque index reçoit un numéro unique, ce qui permet de
+// savoir si un calcul fait sur l'index (un rapport) est encore à jour
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+// Filtres communs à la recherche plein texte et au simple listage
+const SEARCH_FILTERS: &str = "
+    (?2 IS NULL OR e.date >= ?2)
+    AND (?3 IS NULL OR e.date <= ?3)
+    AND (?4 IS NULL OR e.project_key = ?4)
+    AND (?5 IS NULL OR e.entry_type_key = ?5)
+    AND (?6 IS NULL OR EXISTS (
+        SELECT 1 FROM entry_tags t WHERE t.entry = e.id AND t.tag_key = ?6
+    ))";
+
+fn migrate(conn: &Connection) -> Result<()> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (index, sql) in INDEX_MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+fn modified_ns(fingerprint: &FileFingerprint) -> Option<i64> {
+    fingerprint
+        .modified
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_nanos() as i64)
+}
+
+fn filter_key(filter: &Option<String>) -> Option<String> {
+    filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(fold)
+}
+
+// Index SQLite des entrées d'un dossier de journal. Les fichiers Markdown
+// restent la source de vérité : `refresh` relit uniquement les jours dont la
+// date de modification ou la taille ont changé et oublie les jours supprimés.
+pub struct JournalIndex {
+    conn: Connection,
+    db_path: PathBuf,
+    journal_dir: PathBuf,
+    generation: u64,
+}
+
+impl JournalIndex {
+    pub fn open(db_path: &Path, journal_dir: &Path) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Self::init(Connection::open(db_path)?, db_path, journal_dir)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory(journal_dir: &Path) -> Result<Self> {
+        Self::init(
+            Connection::open_in_memory()?,
+            Path::new(":memory:"),
+            journal_dir,
+        )
+    }
+
+    fn init(conn: Connection, db_path: &Path, journal_dir: &Path) -> Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        migrate(&conn)?;
+
+        let index = Self {
+            conn,
+            db_path: db_path.to_path_buf(),
+            journal_dir: journal_dir.to_path_buf(),
+            generation: next_generation(),
+        };
+
+        // Un index construit pour un autre dossier ne vaut plus rien
+        let indexed_dir: Option<String> = index
+            .conn
+            .query_row(
+                "SELECT value FROM index_meta WHERE key = ?1",
+                [JOURNAL_DIR_KEY],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let journal_dir = journal_dir.to_string_lossy().to_string();
+        if indexed_dir.as_deref() != Some(journal_dir.as_str()) {
+            let tx = index.conn.unchecked_transaction()?;
+            tx.execute("DELETE FROM entries_fts", [])?;
+            tx.execute("DELETE FROM journal_days", [])?;
+            tx.execute(
+                "INSERT OR REPLACE INTO index_meta (key, value) VALUES (?1, ?2)",
+                params![JOURNAL_DIR_KEY, journal_dir],
+            )?;
+            tx.commit()?;
+        }
+
+        Ok(index)
+    }
+
+    pub fn is_for(&self, db_path: &Path, journal_dir: &Path) -> bool {
+        self.db_path == db_path && self.journal_dir == journal_dir
+    }
+
+    pub fn refresh(&mut self) -> Result<()> {
+        let files = list_journal_files(&self.journal_dir)?;
+
+        let mut indexed: HashMap<String, (Option<i64>, i64)> = HashMap::new();
+        {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT date, modified_ns, len FROM journal_days")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?)))
+            })?;
+            for row in rows {
+                let (date, fingerprint) = row?;
+                indexed.insert(date, fingerprint);
+            }
+        }
+
+        let tx = self.conn.transaction()?;
+        let mut changed = false;
+        for file in &files {
+            let fingerprint = (modified_ns(&file.fingerprint), file.fingerprint.len as i64);
+            if indexed.remove(&file.date) == Some(fingerprint) {
+                continue;
+            }
+            let content = fs::read_to_string(&file.path)?;
+            index_day(&tx, &file.date, &file.fingerprint, &content)?;
+            changed = true;
+        }
+
+        // Ce qui reste n'existe plus sur le disque
+        for date in indexed.keys() {
+            remove_day(&tx, date)?;
+            changed = true;
+        }
+        tx.commit()?;
+
+        if changed {
+            self.generation = next_generation();
+        }
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn search(&self, query: &SearchQuery) -> Result<Vec<SearchHit>> {
+        let terms = tokenize(&query.text);
+        let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT) as i64;
+
+        let (sql, match_expr) = match fts_query(&terms) {
+            Some(match_expr) => {
+                let weights = FIELDS
+                    .iter()
+                    .map(|field| field.weight().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (
+                    format!(
+                        "SELECT e.date, e.data, -bm25(entries_fts, {}) AS score
+                         FROM entries_fts JOIN entries e ON e.id = entries_fts.rowid
+                         WHERE entries_fts MATCH ?1 AND {}
+                         ORDER BY score DESC, e.date DESC, e.timestamp DESC
+                         LIMIT ?7",
+                        weights, SEARCH_FILTERS
+                    ),
+                    Some(match_expr),
+                )
+            }
+            None => (
+                format!(
+                    "SELECT e.date, e.data, 0.0 FROM entries e
+                     WHERE {}
+                     ORDER BY e.date DESC, e.timestamp DESC
+                     LIMIT ?7",
+                    SEARCH_FILTERS
+                ),
+                None,
+            ),
+        };
+
+        let tag = query
+            .tag
+            .as_deref()
+            .map(|tag| tag.trim().trim_start_matches('#').to_string());
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            params![
+                match_expr,
+                query.start_date,
+                query.end_date,
+                filter_key(&query.project),
+                filter_key(&query.entry_type),
+                filter_key(&tag),
+                limit,
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f64>(2)?,
+                ))
+            },
+        )?;
+
+        let mut hits = Vec::new();
+        for row in rows {
+            let (date, data, score) = row?;
+            let entry: ParsedJournalEntry = serde_json::from_str(&data)?;
+            let (field, snippet) = snippet_for(&entry, &terms);
+            hits.push(SearchHit {
+                date,
+                entry,
+                score,
+                field: field.name().to_string(),
+                snippet,
+            });
+        }
+
+        Ok(hits)
+    }
+
+    // Agrégats des journées locales comprises entre deux dates (incluses).
+    // Une entrée pouvant compter pour la veille ou le lendemain de son fichier,
+    // les fichiers voisins de la période sont lus aussi.
+    pub fn day_aggregates_between(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Vec<DayAggregate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT data FROM day_aggregates
+             WHERE date >= date(?1, '-1 day') AND date <= date(?2, '+1 day')
+             ORDER BY date",
+        )?;
+        let rows = stmt.query_map(params![start_date, end_date], |row| row.get::<_, String>(0))?;
+
+        let mut aggregates = Vec::new();
+        for row in rows {
+            let days: Vec<DayAggregate> = serde_json::from_str(&row?)?;
+            aggregates
+                .extend(days.into_iter().filter(|day| {
+                    day.date.as_str() >= start_date && day.date.as_str() <= end_date
+                }));
+        }
+        Ok(aggregates)
+    }
+}
+
+fn remove_day(tx: &Transaction, date: &str) -> Result<()> {
+    tx.execute(
+        "DELETE FROM entries_fts WHERE rowid IN (SELECT id FROM entries WHERE date = ?1)",
+        [date],
+    )?;
+    // Les entrées, tags et tickets suivent par cascade
+    tx.execute("DELETE FROM journal_days WHERE date = ?1", [date])?;
+    Ok(())
+}
+
+fn index_day(
+    tx: &Transaction,
+    date: &str,
+    fingerprint: &FileFingerprint,
+    content: &str,
+) -> Result<()> {
+    remove_day(tx, date)?;
+    tx.execute(
+        "INSERT INTO journal_days (date, modified_ns, len) VALUES (?1, ?2, ?3)",
+        params![date, modified_ns(fingerprint), fingerprint.len as i64],
+    )?;
+
+    let entries = parse_journal_entries(content);
+    tx.execute(
+        "INSERT INTO day_aggregates (date, data) VALUES (?1, ?2)",
+        params![
+            date,
+            serde_json::to_string(&DayAggregate::for_file(date, &entries))?
+        ],
+    )?;
+
+    for (position, entry) in entries.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO entries (date, position, entry_id, timestamp, project, project_key,
+                                  entry_type, entry_type_key, duration, duration_minutes, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                date,
+                position as i64,
+                entry.id,
+                entry.timestamp,
+                entry.project,
+                fold(entry.project.trim()),
+                entry.entry_type,
+                fold(entry.entry_type.trim()),
+                entry.duration.to_string(),
+                entry.duration.known().map(|duration| duration.minutes()),
+                serde_json::to_string(entry)?,
+            ],
+        )?;
+        let row_id = tx.last_insert_rowid();
+
+        for tag in &entry.tags {
+            tx.execute(
+                "INSERT INTO entry_tags (entry, tag, tag_key) VALUES (?1, ?2, ?3)",
+                params![row_id, tag, fold(tag.trim())],
+            )?;
+        }
+        for ticket in &entry.jira_tickets {
+            tx.execute(
+                "INSERT INTO entry_jira (entry, key, summary) VALUES (?1, ?2, ?3)",
+                params![row_id, ticket.key, ticket.summary],
+            )?;
+        }
+
+        let columns: Vec<String> = FIELDS.iter().map(|field| field.text(entry)).collect();
+        tx.execute(
+            "INSERT INTO entries_fts (rowid, jira, tags, project, description, results,
+                                      blockers, reflections)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row_id, columns[0], columns[1], columns[2], columns[3], columns[4], columns[5],
+                columns[6],
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JournalIndex;
+    use crate::search::SearchQuery;
+    use std::fs;
+
+    fn make_temp_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_day(root: &std::path::Path, date: &str, content: &str) {
+        let dir = root.join(&date[0..4]).join(&date[5..7]);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{}.md", date)), content).unwrap();
+    }
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            ..SearchQuery::default()
+        }
+    }
+
+    #[test]
+    fn finds_and_ranks_entries_across_days() {
+        let root = make_temp_dir();
+        write_day(
+            &root,
+            "2026-03-02",
+            "## 09:00\n**Projet**: Mandate  \n**Description**: Correction de l'export des mandats CSE  \n**Tags**: #bug #export  \n",
+        );
+        write_day(
+            &root,
+            "2026-04-06",
+            "## 10:00\n**Projet**: Claims  \n**Description**: Réunion sur l'export  \n**Tags**: #meeting  \n",
+        );
+
+        let mut index = JournalIndex::open_in_memory(&root).unwrap();
+        index.refresh().unwrap();
+
+        let hits = index.search(&query("export mandat")).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].date, "2026-03-02");
+
+        let hits = index.search(&query("export")).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].entry.project, "Mandate");
+
+        let hits = index.search(&query("reunion")).unwrap();
+        assert_eq!(hits[0].date, "2026-04-06");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn applies_filters() {
+        let root = make_temp_dir();
+        write_day(
+            &root,
+            "2026-03-02",
+            "## 09:00\n**Projet**: Mandate  \n**Type d'activité**: debug  \n**Description**: Export  \n**Tags**: #bug  \n",
+        );
+        write_day(
+            &root,
+            "2026-04-06",
+            "## 10:00\n**Projet**: Claims  \n**Description**: Export  \n**Tags**: #feature  \n",
+        );
+
+        let mut index = JournalIndex::open_in_memory(&root).unwrap();
+        index.refresh().unwrap();
+
+        let by_date = index
+            .search(&SearchQuery {
+                start_date: Some("2026-04-01".to_string()),
+                ..query("export")
+            })
+            .unwrap();
+        assert_eq!(by_date.len(), 1);
+        assert_eq!(by_date[0].date, "2026-04-06");
+
+        let by_tag = index
+            .search(&SearchQuery {
+                tag: Some("#bug".to_string()),
+                ..query("")
+            })
+            .unwrap();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].entry.project, "Mandate");
+
+        let by_type = index
+            .search(&SearchQuery {
+                entry_type: Some("Debug".to_string()),
+                project: Some("mandate".to_string()),
+                ..query("")
+            })
+            .unwrap();
+        assert_eq!(by_type.len(), 1);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reindexes_changed_and_removed_days() {
+        let root = make_temp_dir();
+        write_day(
+            &root,
+            "2026-04-06",
+            "## 09:00\n**Description**: Ancien texte  \n",
+        );
+
+        let mut index = JournalIndex::open_in_memory(&root).unwrap();
+        index.refresh().unwrap();
+        assert_eq!(index.search(&query("ancien")).unwrap().len(), 1);
+
+        write_day(
+            &root,
+            "2026-04-06",
+            "## 09:00\n**Description**: Nouveau texte plus long  \n",
+        );
+        index.refresh().unwrap();
+        assert!(index.search(&query("ancien")).unwrap().is_empty());
+        assert_eq!(index.search(&query("nouveau")).unwrap().len(), 1);
+
+        fs::remove_dir_all(root.join("2026")).unwrap();
+        index.refresh().unwrap();
+        assert!(index.search(&query("nouveau")).unwrap().is_empty());
+        assert!(index
+            .day_aggregates_between("2026-01-01", "2026-12-31")
+            .unwrap()
+            .is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_day_aggregates_and_generation_in_sync_with_files() {
+        let root = make_temp_dir();
+        write_day(
+            &root,
+            "2026-04-06",
+            "## 09:00\n**Projet**: Mandate  \n**Durée**: 90 minutes  \n",
+        );
+
+        let mut index = JournalIndex::open_in_memory(&root).unwrap();
+        index.refresh().unwrap();
+        let generation = index.generation();
+
+        let days = index
+            .day_aggregates_between("2026-04-01", "2026-04-30")
+            .unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].projects["Mandate"].minutes, 90);
+
+        index.refresh().unwrap();
+        assert_eq!(index.generation(), generation);
+
+        write_day(
+            &root,
+            "2026-04-07",
+            "## 09:00\n**Projet**: Claims  \n**Durée**: 30 minutes  \n",
+        );
+        index.refresh().unwrap();
+        assert_ne!(index.generation(), generation);
+        assert_eq!(
+            index
+                .day_aggregates_between("2026-04-01", "2026-04-30")
+                .unwrap()
+                .len(),
+            2
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn persists_and_resets_when_the_journal_dir_changes() {
+        let root = make_temp_dir();
+        let other = make_temp_dir();
+        let db_path = root.join("index.db");
+        write_day(
+            &root,
+            "2026-04-06",
+            "## 09:00\n**Description**: Persistant  \n",
+        );
+
+        JournalIndex::open(&db_path, &root)
+            .unwrap()
+            .refresh()
+            .unwrap();
+
+        let index = JournalIndex::open(&db_path, &root).unwrap();
+        assert_eq!(
+            index
+                .day_aggregates_between("2026-04-06", "2026-04-06")
+                .unwrap()
+                .len(),
+            1
+        );
+        drop(index);
+
+        let index = JournalIndex::open(&db_path, &other).unwrap();
+        assert!(index
+            .day_aggregates_between("2026-04-06", "2026-04-06")
+            .unwrap()
+            .is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+        fs::remove_dir_all(other).unwrap();
+    }
+}
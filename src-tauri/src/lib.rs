@@ -1,140 +1,1117 @@
+mod catalog;
+mod credentials;
 mod database;
+mod duration;
+mod error;
 mod jira;
+mod jira_import;
+mod jira_keys;
+mod jira_update;
+mod logging;
 mod file_manager;
-
+mod journal_index;
+mod markdown;
+mod profile;
+mod provider;
+mod report;
+mod search;
+mod ticket_cache;
+mod time_range;
+mod timestamp;
+mod worklog;
+
+use chrono::Utc;
+use std::collections::BTreeMap;
 use std::sync::Mutex;
 use tauri::{Manager, State};
 
+use crate::credentials::{CredentialBackend, CredentialStatus, CredentialStore, JiraCredentials};
+use crate::catalog::{ALIASES_KEY, Catalog, CatalogKind, NEXT_ID_KEY, rename_in_entry};
 use crate::database::{ActivityType, Project, Tag};
-use crate::jira::{JiraClient, JiraTicket};
-use crate::file_manager::{JournalEntry, ParsedJournalEntry, save_journal_entry, load_journal_file, get_available_journal_dates, parse_journal_entries, update_journal_entry, delete_journal_entry};
-use std::collections::HashMap;
+use crate::jira::{JiraAuth, JiraAuthMethod, JiraClient, JiraDeployment, JiraFieldConfig, JiraTicket, JiraTransition, OAuthRefresh, OAuthTokenSaver, DEFAULT_SEARCH_LIMIT};
+use crate::duration::AmbiguousUnit;
+use crate::error::{AppError, ErrorCode, LOCALE_PREFERENCE, set_locale};
+use crate::file_manager::{DurationMigration, EntryRewrite, JiraLinkRewrite, JournalEntry, rewrite_journal_entries, rewrite_journal_jira_links, load_journal_entries_between, load_journal_entry, migrate_journal_durations, save_journal_entry, load_journal_file, get_available_journal_dates, update_journal_entry, delete_journal_entry, restore_journal_backup};
+use crate::markdown::{JiraTicketRef, ParsedJournalEntry, parse_journal_entries};
+use crate::journal_index::JournalIndex;
+use crate::report::{ActivityReport, ReportCache, apply_aliases, build_activity_report};
+use crate::search::{SearchHit, SearchQuery};
+use crate::ticket_cache::{CachedTicket, TicketCache};
+use crate::logging::{DEFAULT_LOG_FILTER, LOG_FILTER_PREFERENCE};
+use crate::timestamp::{TIMESTAMP_FORMAT_PREFERENCE, TimestampFormat};
+use crate::time_range::{DayValidation, DEFAULT_MAX_DAILY_MINUTES, validate_day as validate_day_entries};
+use crate::jira_keys::KeyDetector;
+use crate::jira_update::{EntryIssueResult, EntryIssueUpdate, apply_entry_update};
+use crate::jira_import::{DraftEntry, draft_entries, local_period, synced_worklog_ids};
+use crate::worklog::{SplitRule, WorklogSync, plan_worklogs, push_worklogs};
+use crate::provider::{DemoProvider, FixtureProvider, IssueProvider, ProviderKind, ProviderPage, TICKET_FIXTURE_PREFERENCE, TICKET_PROVIDER_PREFERENCE};
+use crate::profile::{JiraProfileConfig, Profile, active_journal_dir, active_profile, load_profiles, save_profiles, set_active_profile, validate_root_dir};
 use docx_rs::{Docx, Paragraph, Run};
 
 // État global pour le client Jira et la liste des tickets disponibles
 struct AppState {
     jira_client: Mutex<Option<JiraClient>>,
     available_tickets: Mutex<Vec<JiraTicket>>,
+    // Index SQLite du profil actif, ouvert à la première utilisation
+    journal_index: Mutex<Option<JournalIndex>>,
+    // Cache des tickets Jira du profil actif, ouvert à la première utilisation
+    ticket_cache: Mutex<Option<TicketCache>>,
+    report_cache: Mutex<ReportCache>,
+    // Sérialise les modifications des catalogues
+    catalog_lock: Mutex<()>,
+}
+
+// Donne accès à l'index du profil actif après l'avoir resynchronisé avec les
+// fichiers Markdown. L'index est rouvert si le profil ou son dossier a changé.
+fn with_journal_index<T>(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    f: impl FnOnce(&JournalIndex) -> anyhow::Result<T>,
+) -> Result<T, AppError> {
+    let profile = active_profile(app)?;
+    let db_path = app.path().app_data_dir()?.join(profile.index_file());
+    let journal_dir = profile.journal_dir();
+
+    let mut guard = state.journal_index.lock()?;
+    let index = match guard.take() {
+        Some(index) if index.is_for(&db_path, &journal_dir) => index,
+        _ => JournalIndex::open(&db_path, &journal_dir)?,
+    };
+    let index = guard.insert(index);
+
+    index.refresh()?;
+    f(index).map_err(AppError::from)
+}
+
+// Donne accès au cache des tickets du profil actif, rouvert si le profil a
+// changé
+fn with_ticket_cache<T>(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    f: impl FnOnce(&mut TicketCache) -> anyhow::Result<T>,
+) -> Result<T, AppError> {
+    let db_path = app.path().app_data_dir()?.join(active_profile(app)?.ticket_cache_file());
+
+    let mut guard = state.ticket_cache.lock()?;
+    let cache = match guard.take() {
+        Some(cache) if cache.is_for(&db_path) => cache,
+        _ => TicketCache::open(&db_path)?,
+    };
+    f(guard.insert(cache)).map_err(AppError::from)
 }
 
 // === COMMANDES POUR LE JOURNAL ===
 
+// Détecteur des clés de ticket du profil actif : motifs configurés et clés
+// de projet de `project_map`
+fn key_detector(app: &tauri::AppHandle) -> Result<KeyDetector, AppError> {
+    let patterns: Vec<String> = match active_profile(app)?.jira {
+        Some(jira) => jira
+            .key_patterns
+            .into_iter()
+            .chain(jira.project_map.into_keys().map(|key| regex::escape(&key)))
+            .collect(),
+        None => Vec::new(),
+    };
+    KeyDetector::new(patterns.iter().map(String::as_str))
+}
+
+// Tickets cités dans les textes mais pas encore liés, avec leur résumé s'il
+// est connu du cache ou, avec `fetch_unknown`, de Jira
+async fn detected_tickets(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    texts: &[&str],
+    linked: &[JiraTicketRef],
+    fetch_unknown: bool,
+) -> Result<Vec<JiraTicketRef>, AppError> {
+    let keys = key_detector(app)?.new_keys(texts, linked);
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let known = lookup_tickets(app, state, &keys, fetch_unknown).await?;
+    Ok(keys
+        .into_iter()
+        .map(|key| {
+            let summary = known
+                .iter()
+                .find(|known| known.ticket.key == key)
+                .map(|known| known.ticket.fields.summary.clone())
+                .filter(|summary| !summary.is_empty());
+            JiraTicketRef { key, summary, url: None }
+        })
+        .collect())
+}
+
+// Ajoute les tickets cités dans la description, les résultats et les
+// blocages. Une détection impossible n'empêche pas l'enregistrement. Pour ne
+// pas faire attendre l'enregistrement, les résumés viennent du cache seul :
+// les tickets inconnus sont demandés à Jira ensuite.
+async fn link_detected_tickets(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    texts: &[&str],
+    tickets: &mut Vec<JiraTicketRef>,
+) {
+    match detected_tickets(app, state, texts, tickets, false).await {
+        Ok(detected) => {
+            let unknown: Vec<String> = detected
+                .iter()
+                .filter(|ticket| ticket.summary.is_none())
+                .map(|ticket| ticket.key.clone())
+                .collect();
+            if !unknown.is_empty() {
+                spawn_ticket_lookup(app.clone(), unknown);
+            }
+            tickets.extend(detected);
+        }
+        Err(e) => tracing::warn!("Jira keys not detected: {}", e),
+    }
+}
+
+fn spawn_ticket_lookup(app: tauri::AppHandle, keys: Vec<String>) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if let Err(e) = lookup_tickets(&app, &state, &keys, true).await {
+            tracing::debug!("Jira ticket lookup postponed: {}", e);
+        }
+    });
+}
+
+// Tickets qu'un enregistrement ajouterait, pour les faire valider avant :
+// l'entrée est ensuite enregistrée avec `detect_keys: false`
 #[tauri::command]
-async fn save_journal_entry_cmd(date: String, entry: JournalEntry) -> Result<(), String> {
-    save_journal_entry(&date, entry).map_err(|e| e.to_string())
+async fn detect_jira_keys(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    description: String,
+    results: String,
+    blockers: String,
+    jira_tickets: Vec<JiraTicketRef>,
+) -> Result<Vec<JiraTicketRef>, AppError> {
+    detected_tickets(&app, &state, &[&description, &results, &blockers], &jira_tickets, true).await
+}
+
+// Sauf `detect_keys: false`, les tickets cités dans le texte sont liés
+#[tauri::command]
+async fn save_journal_entry_cmd(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    date: String,
+    mut entry: JournalEntry,
+    detect_keys: Option<bool>,
+) -> Result<(), AppError> {
+    if detect_keys.unwrap_or(true) {
+        let texts = [entry.description.as_str(), entry.results.as_str(), entry.blockers.as_str()];
+        let mut tickets = entry.jira_tickets.clone();
+        link_detected_tickets(&app, &state, &texts, &mut tickets).await;
+        entry.jira_tickets = tickets;
+    }
+    let journal_dir = active_journal_dir(&app)?;
+    let store = app.store("store.json")?;
+    let timestamp_format = TimestampFormat::from_preference(
+        store.get(TIMESTAMP_FORMAT_PREFERENCE).as_ref().and_then(|value| value.as_str())
+    );
+    let base_url = jira_link_base(&app, &state);
+    save_journal_entry(&journal_dir, &date, entry, timestamp_format, base_url.as_deref()).map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn load_journal_file_cmd(date: String) -> Result<String, String> {
-    load_journal_file(&date).map_err(|e| e.to_string())
+async fn load_journal_file_cmd(app: tauri::AppHandle, date: String) -> Result<String, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    load_journal_file(&journal_dir, &date).map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn get_journal_dates() -> Result<Vec<String>, String> {
-    get_available_journal_dates().map_err(|e| e.to_string())
+async fn get_journal_dates(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    get_available_journal_dates(&journal_dir).map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn parse_journal_entries_cmd(date: String) -> Result<Vec<ParsedJournalEntry>, String> {
-    let content = load_journal_file(&date).map_err(|e| e.to_string())?;
+async fn parse_journal_entries_cmd(app: tauri::AppHandle, date: String) -> Result<Vec<ParsedJournalEntry>, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    let content = load_journal_file(&journal_dir, &date)?;
     Ok(parse_journal_entries(&content))
 }
 
 #[tauri::command]
 async fn update_journal_entry_cmd(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
     date: String, 
-    entry_index: usize, 
-    updated_entry: ParsedJournalEntry
-) -> Result<bool, String> {
-    update_journal_entry(&date, entry_index, &updated_entry).map_err(|e| e.to_string())
+    entry_id: String, 
+    mut updated_entry: ParsedJournalEntry,
+    detect_keys: Option<bool>,
+) -> Result<(), AppError> {
+    if detect_keys.unwrap_or(true) {
+        let texts = [updated_entry.description.as_str(), updated_entry.results.as_str(), updated_entry.blockers.as_str()];
+        let mut tickets = updated_entry.jira_tickets.clone();
+        link_detected_tickets(&app, &state, &texts, &mut tickets).await;
+        updated_entry.jira_tickets = tickets;
+    }
+    let journal_dir = active_journal_dir(&app)?;
+    let base_url = jira_link_base(&app, &state);
+    update_journal_entry(&journal_dir, &date, &entry_id, &updated_entry, base_url.as_deref()).map_err(AppError::from)
 }
 
 #[tauri::command]
-async fn delete_journal_entry_cmd(date: String, entry_index: usize) -> Result<bool, String> {
-    delete_journal_entry(&date, entry_index).map_err(|e| e.to_string())
+async fn delete_journal_entry_cmd(app: tauri::AppHandle, date: String, entry_id: String) -> Result<(), AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    delete_journal_entry(&journal_dir, &date, &entry_id).map_err(AppError::from)
+}
+
+#[tauri::command]
+async fn restore_journal_backup_cmd(app: tauri::AppHandle, date: String) -> Result<(), AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    restore_journal_backup(&journal_dir, &date).map_err(AppError::from)
+}
+
+// Contrôle la cohérence d'une journée : chevauchements et trous entre plages
+// horaires, durées qui ne correspondent pas à leur plage, total trop élevé
+// (préférence `max_daily_minutes`)
+#[tauri::command]
+async fn validate_day(app: tauri::AppHandle, date: String) -> Result<DayValidation, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    let content = load_journal_file(&journal_dir, &date)?;
+
+    let store = app.store("store.json")?;
+    // Enregistrée comme nombre ou comme texte selon le formulaire
+    let max_minutes = store
+        .get("max_daily_minutes")
+        .and_then(|value| {
+            value
+                .as_u64()
+                .and_then(|minutes| u32::try_from(minutes).ok())
+                .or_else(|| value.as_str()?.trim().parse().ok())
+        })
+        .unwrap_or(DEFAULT_MAX_DAILY_MINUTES);
+
+    Ok(validate_day_entries(&date, &parse_journal_entries(&content), max_minutes))
+}
+
+// Réécrit les durées de tout le journal sous leur forme canonique. En
+// simulation (`dry_run`), rien n'est écrit : seul le bilan est renvoyé.
+#[tauri::command]
+async fn migrate_durations(
+    app: tauri::AppHandle,
+    ambiguous_unit: Option<AmbiguousUnit>,
+    dry_run: bool,
+) -> Result<DurationMigration, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    migrate_journal_durations(&journal_dir, ambiguous_unit, dry_run).map_err(AppError::from)
+}
+
+// Adresse Jira des liens écrits dans le journal : celle du client connecté,
+// à défaut celle du profil actif. Sans l'une ni l'autre, les liens sont
+// relatifs.
+fn jira_link_base(app: &tauri::AppHandle, state: &AppState) -> Option<String> {
+    state
+        .jira_client
+        .lock()
+        .ok()
+        .and_then(|client| client.as_ref().map(|client| client.base_url().to_string()))
+        .filter(|base_url| !base_url.is_empty())
+        .or_else(|| active_profile(app).ok().and_then(|profile| profile.jira).map(|jira| jira.base_url))
+        .filter(|base_url| !base_url.is_empty())
+}
+
+// Fait pointer les liens Jira de tout le journal vers l'instance actuelle,
+// ou seulement ceux de `previous_base_url` (et les liens relatifs). En
+// simulation (`dry_run`), rien n'est écrit : seul le bilan est renvoyé.
+#[tauri::command]
+async fn rewrite_jira_links(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    previous_base_url: Option<String>,
+    dry_run: bool,
+) -> Result<JiraLinkRewrite, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    let base_url = jira_link_base(&app, &state);
+    rewrite_journal_jira_links(&journal_dir, base_url.as_deref(), previous_base_url.as_deref(), dry_run).map_err(AppError::from)
+}
+
+// Recherche plein texte sur tout le journal du profil actif. L'index est mis à
+// jour à chaque requête, en ne relisant que les fichiers modifiés.
+#[tauri::command]
+async fn search_journal(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    query: SearchQuery
+) -> Result<Vec<SearchHit>, AppError> {
+    with_journal_index(&app, &state, |index| index.search(&query))
+}
+
+// === COMMANDES POUR LES PROFILS ===
+
+#[tauri::command]
+async fn list_profiles(app: tauri::AppHandle) -> Result<Vec<Profile>, AppError> {
+    load_profiles(&app)
+}
+
+#[tauri::command]
+async fn get_active_profile(app: tauri::AppHandle) -> Result<Profile, AppError> {
+    active_profile(&app)
+}
+
+#[tauri::command]
+async fn create_profile(
+    app: tauri::AppHandle,
+    name: String,
+    root_dir: String,
+    jira: Option<JiraProfileConfig>,
+) -> Result<Profile, AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::invalid_field("name", "Le nom du profil est obligatoire"));
+    }
+    let root_dir = validate_root_dir(&root_dir)?;
+
+    let mut profiles = load_profiles(&app)?;
+    let profile = Profile {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.trim().to_string(),
+        root_dir: root_dir.to_string_lossy().to_string(),
+        jira,
+    };
+    profiles.push(profile.clone());
+    save_profiles(&app, &profiles)?;
+
+    Ok(profile)
+}
+
+#[tauri::command]
+async fn update_profile(
+    app: tauri::AppHandle,
+    id: String,
+    name: String,
+    root_dir: String,
+    jira: Option<JiraProfileConfig>,
+) -> Result<Profile, AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::invalid_field("name", "Le nom du profil est obligatoire"));
+    }
+    let root_dir = validate_root_dir(&root_dir)?;
+
+    let mut profiles = load_profiles(&app)?;
+    let profile = profiles
+        .iter_mut()
+        .find(|profile| profile.id == id)
+        .ok_or_else(|| AppError::not_found("Profil non trouvé"))?;
+    profile.name = name.trim().to_string();
+    profile.root_dir = root_dir.to_string_lossy().to_string();
+    profile.jira = jira;
+    let updated = profile.clone();
+    save_profiles(&app, &profiles)?;
+
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn switch_profile(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Profile, AppError> {
+    let profile = set_active_profile(&app, &id)?;
+
+    // Le client Jira et les tickets chargés appartiennent à l'ancien profil
+    *state.jira_client.lock()? = None;
+    state.available_tickets.lock()?.clear();
+    // Identifiants du nouveau profil, sauf s'ils attendent une phrase de passe
+    if let Err(e) = connect_saved_jira(&app, &state, None).await {
+        tracing::warn!("saved Jira credentials not loaded: {}", e);
+    }
+
+    Ok(profile)
 }
 
 // === COMMANDES POUR JIRA ===
 
+fn jira_not_initialized() -> AppError {
+    AppError::new(ErrorCode::JiraNotConfigured, "Client Jira non initialisé")
+}
+
+// Source des tickets choisie dans les préférences. Sans client Jira configuré,
+// la source Jira renvoie une erreur : les données de démonstration ne sont
+// utilisées que si elles ont été choisies.
+fn ticket_provider(app: &tauri::AppHandle, state: &AppState) -> Result<Box<dyn IssueProvider>, AppError> {
+    let store = app.store("store.json")?;
+    let preference = |key: &str| store.get(key).and_then(|value| value.as_str().map(str::to_string));
+
+    match ProviderKind::from_preference(preference(TICKET_PROVIDER_PREFERENCE).as_deref())? {
+        ProviderKind::Jira => {
+            let client = state
+                .jira_client
+                .lock()?
+                .clone()
+                .filter(JiraClient::is_configured)
+                .ok_or_else(jira_not_initialized)?;
+            let fields = active_profile(app)?
+                .jira
+                .map(|jira| jira.fields)
+                .unwrap_or_default();
+            Ok(Box::new(client.with_fields(fields)))
+        }
+        ProviderKind::Demo => Ok(Box::new(DemoProvider::default())),
+        ProviderKind::Fixture => {
+            let path = preference(TICKET_FIXTURE_PREFERENCE)
+                .filter(|path| !path.trim().is_empty())
+                .ok_or_else(|| AppError::invalid_field(TICKET_FIXTURE_PREFERENCE, "Aucun fichier de tickets choisi"))?;
+            Ok(Box::new(FixtureProvider::load(std::path::Path::new(&path))?))
+        }
+    }
+}
+
+// Les paramètres viennent un à un du frontend
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 async fn initialize_jira(
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
     base_url: String,
     email: String,
     api_token: String,
-) -> Result<(), String> {
-    println!("Manually initializing Jira with: base_url='{}', email='{}', token='{}'", 
-        base_url, 
-        email, 
-        if api_token.is_empty() { "empty" } else { "***set***" }
-    );
-    let client = JiraClient::new(base_url, email, api_token);
-    let mut jira_client = state.jira_client.lock().unwrap();
-    *jira_client = Some(client);
+    auth_method: Option<JiraAuthMethod>,
+    deployment: Option<JiraDeployment>,
+    cloud_id: Option<String>,
+) -> Result<(), AppError> {
+    let auth_method = auth_method.unwrap_or_default();
+    tracing::info!(base_url = %base_url, auth = ?auth_method, "initializing Jira");
+    let auth = JiraAuth::from_method(auth_method, email.clone(), api_token, cloud_id.clone())
+        .ok_or_else(|| AppError::validation("Configuration Jira incomplète"))?;
+    let profile_id = active_profile(&app)?.id;
+    let mut profiles = load_profiles(&app)?;
+    let http = profiles
+        .iter()
+        .find(|profile| profile.id == profile_id)
+        .and_then(|profile| profile.jira.as_ref())
+        .map(|jira| jira.http.clone())
+        .unwrap_or_default();
+    let mut client = JiraClient::with_auth(base_url.clone(), Some(auth)).with_http(http)?;
+
+    // Sans type de déploiement explicite, interroger l'instance, et à défaut
+    // se fier à son adresse
+    let deployment = match deployment {
+        Some(deployment) => deployment,
+        None => client.detect_deployment().await.unwrap_or_else(|_| client.deployment()),
+    };
+    client = client.with_deployment(deployment);
+
+    // Mémoriser l'instance et l'identifiant (jamais le token) dans le profil actif
+    let mut fields = JiraFieldConfig::default();
+    if let Some(profile) = profiles.iter_mut().find(|profile| profile.id == profile_id) {
+        let jira = profile.jira.get_or_insert_with(JiraProfileConfig::default);
+        jira.base_url = base_url;
+        jira.email = email;
+        jira.deployment = Some(client.deployment());
+        jira.auth_method = auth_method;
+        jira.cloud_id = cloud_id;
+        fields = jira.fields.clone();
+        save_profiles(&app, &profiles)?;
+    }
+
+    let client = client.with_fields(fields);
+    *state.jira_client.lock()? = Some(client);
+    Ok(())
+}
+
+// === IDENTIFIANTS JIRA ===
+
+fn credential_store(app: &tauri::AppHandle, profile: &Profile) -> Result<CredentialStore, AppError> {
+    let dir = app.path().app_data_dir()?.join("credentials");
+    Ok(CredentialStore::new(&profile.id, &dir))
+}
+
+// Le trousseau du système peut bloquer : il est interrogé hors des tâches async
+async fn with_credential_store<T: Send + 'static>(
+    store: CredentialStore,
+    f: impl FnOnce(&CredentialStore) -> anyhow::Result<T> + Send + 'static,
+) -> Result<T, AppError> {
+    tauri::async_runtime::spawn_blocking(move || f(&store))
+        .await?
+        .map_err(AppError::from)
+}
+
+// Enregistre les jetons OAuth renouvelés, avec la phrase de passe qui a servi
+// à lire ou enregistrer les identifiants : elle n'est gardée qu'en mémoire
+fn oauth_token_saver(store: CredentialStore, username: String, passphrase: Option<String>) -> OAuthTokenSaver {
+    std::sync::Arc::new(move |access_token: &str, refresh: &OAuthRefresh| {
+        let store = store.clone();
+        let passphrase = passphrase.clone();
+        let credentials = JiraCredentials {
+            username: username.clone(),
+            secret: access_token.to_string(),
+            oauth: Some(refresh.clone()),
+        };
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = store.store(&credentials, passphrase.as_deref()) {
+                tracing::warn!("renewed Jira OAuth tokens not saved: {}", e);
+            }
+        });
+    })
+}
+
+// Client Jira construit avec la configuration du profil et ses identifiants,
+// lus dans `store` avec `passphrase`
+fn client_from_credentials(
+    profile: &Profile,
+    credentials: JiraCredentials,
+    store: &CredentialStore,
+    passphrase: Option<String>,
+) -> Result<Option<JiraClient>, AppError> {
+    let Some(jira) = profile.jira.as_ref().filter(|jira| !jira.base_url.is_empty()) else {
+        return Ok(None);
+    };
+    let Some(auth) = JiraAuth::from_method(jira.auth_method, credentials.username.clone(), credentials.secret, jira.cloud_id.clone()) else {
+        return Ok(None);
+    };
+    let mut client = JiraClient::with_auth(jira.base_url.clone(), Some(auth))
+        .with_fields(jira.fields.clone())
+        .with_http(jira.http.clone())?;
+    if let Some(deployment) = jira.deployment {
+        client = client.with_deployment(deployment);
+    }
+    if let Some(refresh) = credentials.oauth {
+        let saver = oauth_token_saver(store.clone(), credentials.username, passphrase);
+        client = client.with_oauth_refresh(refresh, Some(saver));
+    }
+    Ok(Some(client))
+}
+
+// Connecte Jira avec les identifiants enregistrés du profil actif. Renvoie
+// false si rien n'est enregistré ou si le profil n'a pas de configuration Jira.
+async fn connect_saved_jira(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    passphrase: Option<String>,
+) -> Result<bool, AppError> {
+    let profile = active_profile(app)?;
+    let store = credential_store(app, &profile)?;
+    let unlock = passphrase.clone();
+    let credentials = with_credential_store(store.clone(), move |store| store.load(unlock.as_deref())).await?;
+
+    let client = match credentials {
+        Some(credentials) => client_from_credentials(&profile, credentials, &store, passphrase)?,
+        None => None,
+    };
+    match client {
+        Some(client) => {
+            *state.jira_client.lock()? = Some(client);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// Configuration Jira lue dans l'environnement (.env), dépréciée au profit des
+// identifiants enregistrés du profil
+struct EnvJiraConfig {
+    base_url: String,
+    auth_method: JiraAuthMethod,
+    deployment: Option<JiraDeployment>,
+    cloud_id: Option<String>,
+    credentials: JiraCredentials,
+}
+
+impl EnvJiraConfig {
+    fn from_env() -> Option<Self> {
+        let base_url = std::env::var("JIRA_BASE_URL").unwrap_or_default();
+        let email = std::env::var("JIRA_EMAIL").unwrap_or_default();
+        let api_token = std::env::var("JIRA_API_TOKEN").unwrap_or_default();
+        // basic (défaut), bearer ou oauth ; cloud ou server
+        let auth_method = std::env::var("JIRA_AUTH_METHOD")
+            .ok()
+            .and_then(|method| serde_json::from_value::<JiraAuthMethod>(serde_json::Value::from(method.to_lowercase())).ok())
+            .unwrap_or_default();
+        let deployment = std::env::var("JIRA_DEPLOYMENT")
+            .ok()
+            .and_then(|deployment| serde_json::from_value::<JiraDeployment>(serde_json::Value::from(deployment.to_lowercase())).ok());
+        // Site Jira Cloud et application Atlassian du jeton OAuth
+        let cloud_id = std::env::var("JIRA_CLOUD_ID").ok().filter(|cloud_id| !cloud_id.is_empty());
+        let oauth = match (
+            std::env::var("JIRA_OAUTH_CLIENT_ID"),
+            std::env::var("JIRA_OAUTH_CLIENT_SECRET"),
+            std::env::var("JIRA_OAUTH_REFRESH_TOKEN"),
+        ) {
+            (Ok(client_id), Ok(client_secret), Ok(refresh_token)) if !refresh_token.is_empty() => Some(OAuthRefresh {
+                client_id,
+                client_secret,
+                refresh_token,
+                expires_at: None,
+            }),
+            _ => None,
+        };
+
+        tracing::info!(
+            base_url = %base_url,
+            auth = ?auth_method,
+            token_set = !api_token.is_empty(),
+            "loading Jira config from environment"
+        );
+
+        JiraAuth::from_method(auth_method, email.clone(), api_token.clone(), cloud_id.clone())
+            .filter(|_| !base_url.is_empty())
+            .map(|_| Self {
+                base_url,
+                auth_method,
+                deployment,
+                cloud_id,
+                credentials: JiraCredentials { username: email, secret: api_token, oauth },
+            })
+    }
+
+    // Client construit directement avec ces identifiants, quand ils n'ont pas
+    // pu être enregistrés
+    fn client(self, profile: Option<Profile>) -> Option<JiraClient> {
+        let auth = JiraAuth::from_method(self.auth_method, self.credentials.username, self.credentials.secret, self.cloud_id)?;
+        let profile_jira = profile.and_then(|profile| profile.jira);
+        // Le type mémorisé dans le profil ne vaut que pour la même instance
+        let deployment = self.deployment.or_else(|| {
+            profile_jira
+                .as_ref()
+                .filter(|jira| jira.base_url == self.base_url)
+                .and_then(|jira| jira.deployment)
+        });
+        let (fields, http) = profile_jira.map(|jira| (jira.fields, jira.http)).unwrap_or_default();
+        let mut client = JiraClient::with_auth(self.base_url, Some(auth)).with_fields(fields);
+        client = match client.clone().with_http(http) {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!("Jira HTTP settings ignored: {}", e);
+                client
+            }
+        };
+        if let Some(deployment) = deployment {
+            client = client.with_deployment(deployment);
+        }
+        // Les jetons renouvelés ne valent alors que pour la session
+        if let Some(refresh) = self.credentials.oauth {
+            client = client.with_oauth_refresh(refresh, None);
+        }
+        Some(client)
+    }
+}
+
+// Enregistre les identifiants de l'environnement pour le profil actif, avec
+// son instance Jira. Renvoie false si le profil a déjà des identifiants
+// enregistrés : ils sont conservés.
+async fn save_env_jira_credentials(app: &tauri::AppHandle, env: &EnvJiraConfig) -> Result<bool, AppError> {
+    let profile = active_profile(app)?;
+    let store = credential_store(app, &profile)?;
+    let credentials = env.credentials.clone();
+    let saved = with_credential_store(store, move |store| {
+        if store.status().backend.is_some() {
+            return Ok(false);
+        }
+        store.store(&credentials, None).map(|_| true)
+    })
+    .await?;
+    if !saved {
+        return Ok(false);
+    }
+
+    let mut profiles = load_profiles(app)?;
+    if let Some(saved) = profiles.iter_mut().find(|saved| saved.id == profile.id) {
+        let jira = saved.jira.get_or_insert_with(JiraProfileConfig::default);
+        if jira.base_url != env.base_url {
+            jira.deployment = None;
+        }
+        jira.base_url = env.base_url.clone();
+        jira.email = env.credentials.username.clone();
+        jira.auth_method = env.auth_method;
+        jira.cloud_id = env.cloud_id.clone();
+        jira.deployment = env.deployment.or(jira.deployment);
+        save_profiles(app, &profiles)?;
+    }
+    Ok(true)
+}
+
+// Connecte Jira au démarrage. Les identifiants enregistrés priment ; ceux de
+// l'environnement sont enregistrés si le profil n'en a pas encore, et ne
+// servent tels quels que si rien d'enregistré n'est utilisable.
+async fn connect_jira_at_startup(app: &tauri::AppHandle) -> Result<(), AppError> {
+    let state = app.state::<AppState>();
+    let env = EnvJiraConfig::from_env();
+    if let Some(env) = &env {
+        tracing::warn!("Jira credentials in the environment are deprecated, store them with the application instead");
+        match save_env_jira_credentials(app, env).await {
+            Ok(true) => tracing::info!("Jira credentials from the environment saved for the active profile"),
+            Ok(false) => tracing::info!("saved Jira credentials take precedence over the environment"),
+            Err(e) => tracing::warn!("Jira credentials from the environment not saved: {}", e),
+        }
+    }
+
+    match connect_saved_jira(app, &state, None).await {
+        Ok(true) => return Ok(()),
+        Ok(false) => {}
+        Err(e) => tracing::warn!("saved Jira credentials not loaded: {}", e),
+    }
+    if let Some(client) = env.and_then(|env| env.client(active_profile(app).ok())) {
+        *state.jira_client.lock()? = Some(client);
+    }
     Ok(())
 }
 
+// Enregistre les identifiants du profil actif : dans le trousseau du système,
+// sinon dans un fichier chiffré avec `passphrase`. `oauth` accompagne un jeton
+// d'accès OAuth pour le renouveler.
+#[tauri::command]
+async fn store_jira_credentials(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    email: String,
+    api_token: String,
+    oauth: Option<OAuthRefresh>,
+    passphrase: Option<String>,
+) -> Result<CredentialBackend, AppError> {
+    let profile = active_profile(&app)?;
+    let credentials = JiraCredentials { username: email, secret: api_token, oauth };
+    let store = credential_store(&app, &profile)?;
+    let (stored, unlock) = (credentials.clone(), passphrase.clone());
+    let backend = with_credential_store(store.clone(), move |store| store.store(&stored, unlock.as_deref())).await?;
+
+    if let Some(client) = client_from_credentials(&profile, credentials, &store, passphrase)? {
+        *state.jira_client.lock()? = Some(client);
+    }
+    Ok(backend)
+}
+
+// Déverrouille le fichier chiffré et connecte Jira
+#[tauri::command]
+async fn unlock_jira_credentials(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<bool, AppError> {
+    connect_saved_jira(&app, &state, Some(passphrase)).await
+}
+
+// Remplace le token enregistré ; `new_passphrase` change au passage celle du
+// fichier chiffré
+#[tauri::command]
+async fn rotate_jira_token(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    api_token: String,
+    passphrase: Option<String>,
+    new_passphrase: Option<String>,
+) -> Result<CredentialBackend, AppError> {
+    let profile = active_profile(&app)?;
+    let store = credential_store(&app, &profile)?;
+    let passphrase_after = new_passphrase.clone().or_else(|| passphrase.clone());
+    let (backend, credentials) = with_credential_store(store.clone(), move |store| {
+        let mut credentials = store
+            .load(passphrase.as_deref())?
+            .ok_or_else(|| anyhow::anyhow!("Aucun identifiant Jira enregistré"))?;
+        credentials.secret = api_token;
+        // L'expiration connue était celle de l'ancien jeton d'accès
+        if let Some(oauth) = credentials.oauth.as_mut() {
+            oauth.expires_at = None;
+        }
+        let backend = store.store(&credentials, new_passphrase.or(passphrase).as_deref())?;
+        Ok((backend, credentials))
+    })
+    .await?;
+
+    if let Some(client) = client_from_credentials(&profile, credentials, &store, passphrase_after)? {
+        *state.jira_client.lock()? = Some(client);
+    }
+    Ok(backend)
+}
+
+#[tauri::command]
+async fn clear_jira_credentials(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let profile = active_profile(&app)?;
+    let store = credential_store(&app, &profile)?;
+    with_credential_store(store, |store| store.clear()).await?;
+    *state.jira_client.lock()? = None;
+    Ok(())
+}
+
+#[tauri::command]
+async fn jira_credentials_status(app: tauri::AppHandle) -> Result<CredentialStatus, AppError> {
+    let profile = active_profile(&app)?;
+    let store = credential_store(&app, &profile)?;
+    with_credential_store(store, |store| Ok(store.status())).await
+}
+
 #[tauri::command]
 async fn test_jira_connection(
     state: State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let client_option = {
-        let jira_client = state.jira_client.lock().unwrap();
+        let jira_client = state.jira_client.lock()?;
         jira_client.clone()
     };
     
     match client_option {
         Some(client) => {
             // Test avec une requête simple
-            match client.search_tickets("ORDER BY created DESC").await {
-                Ok(tickets) => Ok(format!("Connexion réussie ! Trouvé {} tickets.", tickets.len())),
-                Err(e) => Err(format!("Erreur de connexion: {}", e))
+            match client.search_tickets("ORDER BY created DESC", DEFAULT_SEARCH_LIMIT, None).await {
+                Ok(page) => Ok(format!(
+                    "Connexion réussie ! Trouvé {}{} tickets.",
+                    page.tickets.len(),
+                    if page.has_more { "+" } else { "" }
+                )),
+                Err(e) => {
+                    let error = AppError::from(e);
+                    Err(AppError { message: format!("Erreur de connexion: {}", error.message), ..error })
+                }
             }
         }
-        None => Err("Client Jira non initialisé".to_string())
+        None => Err(jira_not_initialized())
     }
 }
 
+// Recherche paginée : sans `page_token`, la liste des tickets disponibles est
+// remplacée ; avec, la page suivante y est ajoutée
 #[tauri::command]
 async fn fetch_jira_tickets(
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
     query: String,
-) -> Result<Vec<JiraTicket>, String> {
-    // Clone the client instead of keeping the lock across await
-    let client_option = {
-        let jira_client = state.jira_client.lock().unwrap();
-        jira_client.clone()
-    };
-    
-    let tickets = match client_option {
-        Some(client) => {
-            client.search_tickets(&query).await.map_err(|e| e.to_string())?
-        }
-        None => {
-            // Client pas initialisé, utiliser un client mock
-            let mock_client = JiraClient::new("".to_string(), "".to_string(), "".to_string());
-            mock_client.search_tickets(&query).await.map_err(|e| e.to_string())?
-        }
-    };
+    limit: Option<usize>,
+    page_token: Option<String>,
+) -> Result<ProviderPage, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    let next_page = page_token.is_some();
+
+    // La source est construite avant l'await : aucun verrou n'est gardé
+    let provider = ticket_provider(&app, &state)?;
+    let result = provider.search(&query, limit, page_token).await?;
+    // Seuls les tickets venus de Jira vont dans le cache
+    if result.provider == ProviderKind::Jira {
+        with_ticket_cache(&app, &state, |cache| cache.store(&result.page.tickets, Utc::now()))?;
+    }
     
     // Mettre à jour la liste des tickets disponibles
     {
-        let mut available_tickets = state.available_tickets.lock().unwrap();
-        *available_tickets = tickets.clone();
+        let mut available_tickets = state.available_tickets.lock()?;
+        if !next_page {
+            available_tickets.clear();
+        }
+        available_tickets.extend(result.page.tickets.iter().cloned());
     }
     
-    Ok(tickets)
+    Ok(result)
+}
+
+// Envoie vers Jira le temps des entrées de la période (ou des seules entrées
+// `entry_ids`) sous forme de worklogs. En simulation (`dry_run`), renvoie les
+// worklogs qui seraient créés, mis à jour ou supprimés sans rien envoyer.
+#[tauri::command]
+async fn sync_worklogs(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    entry_ids: Option<Vec<String>>,
+    split_rule: Option<SplitRule>,
+    dry_run: bool,
+) -> Result<WorklogSync, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    let days = load_journal_entries_between(&journal_dir, &start_date, &end_date)?;
+    let plan = plan_worklogs(&days, entry_ids.as_deref(), split_rule.unwrap_or_default());
+    if dry_run {
+        return Ok(plan);
+    }
+
+    let client = {
+        let jira_client = state.jira_client.lock()?;
+        jira_client.clone()
+    }
+    .ok_or_else(jira_not_initialized)?;
+    Ok(push_worklogs(&client, &journal_dir, plan).await)
+}
+
+// Propose des entrées à partir de mon activité Jira de la période (worklogs,
+// changements de statut, commentaires). Rien n'est écrit : chaque brouillon
+// accepté est enregistré avec `save_journal_entry_cmd`.
+#[tauri::command]
+async fn draft_entries_from_jira(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<DraftEntry>, AppError> {
+    let (from, to) = local_period(&start_date, &end_date).ok_or_else(|| AppError::invalid_field("start_date", "Période invalide"))?;
+    let profile = active_profile(&app)?;
+    let days = load_journal_entries_between(&profile.journal_dir(), &start_date, &end_date)?;
+    let project_map = profile.jira.map(|jira| jira.project_map).unwrap_or_default();
+
+    let client = {
+        let jira_client = state.jira_client.lock()?;
+        jira_client.clone()
+    }
+    .ok_or_else(jira_not_initialized)?;
+    let activities = client.fetch_my_activity(from, to).await?;
+
+    Ok(draft_entries(activities, &project_map, &synced_worklog_ids(&days)))
+}
+
+fn jira_client(state: &AppState) -> Result<JiraClient, AppError> {
+    state.jira_client.lock()?.clone().ok_or_else(jira_not_initialized)
+}
+
+// Relit un ticket modifié depuis l'application pour que le cache montre son
+// nouveau statut ; un échec attendra le rafraîchissement suivant
+async fn refresh_cached_ticket(app: &tauri::AppHandle, state: &AppState, client: &JiraClient, key: &str) {
+    match client.get_ticket(key).await {
+        Ok(Some(ticket)) => {
+            if let Err(e) = with_ticket_cache(app, state, |cache| cache.store(&[ticket], Utc::now())) {
+                tracing::warn!("Jira ticket cache not updated: {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::debug!(key = %key, "Jira ticket not reloaded: {}", e),
+    }
+}
+
+// Transitions possibles depuis le statut actuel du ticket
+#[tauri::command]
+async fn list_jira_transitions(
+    state: State<'_, AppState>,
+    issue_key: String,
+) -> Result<Vec<JiraTransition>, AppError> {
+    let client = jira_client(&state)?;
+    client.list_transitions(&issue_key).await.map_err(AppError::from)
+}
+
+#[tauri::command]
+async fn transition_jira_issue(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    issue_key: String,
+    transition_id: String,
+) -> Result<(), AppError> {
+    let client = jira_client(&state)?;
+    client.transition_issue(&issue_key, &transition_id).await?;
+    refresh_cached_ticket(&app, &state, &client, &issue_key).await;
+    Ok(())
+}
+
+// À l'enregistrement d'une entrée : publie ses résultats et blocages en
+// commentaire et/ou applique une transition sur les tickets qu'elle
+// référence. Chaque ticket a son propre bilan : un échec n'empêche pas de
+// traiter les suivants ni ne fait perdre ce qui a déjà été fait.
+#[tauri::command]
+async fn update_jira_from_entry(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    date: String,
+    entry_id: String,
+    updates: Vec<EntryIssueUpdate>,
+) -> Result<Vec<EntryIssueResult>, AppError> {
+    let journal_dir = active_journal_dir(&app)?;
+    let entry = load_journal_entry(&journal_dir, &date, &entry_id)?;
+    let client = jira_client(&state)?;
+
+    let mut results = Vec::new();
+    for update in &updates {
+        let result = apply_entry_update(&client, &entry, update).await;
+        if let Some(error) = &result.error {
+            tracing::warn!("Jira update of {} failed: {}", result.issue_key, error);
+        }
+        if result.transitioned {
+            refresh_cached_ticket(&app, &state, &client, &result.issue_key).await;
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+// Tickets de `keys` connus du cache. Avec `fetch_unknown`, ceux qui n'y sont
+// pas encore sont demandés à Jira s'il est joignable, sinon simplement omis.
+// Avec une autre source, les tickets viennent d'elle seule.
+async fn lookup_tickets(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    keys: &[String],
+    fetch_unknown: bool,
+) -> Result<Vec<CachedTicket>, AppError> {
+    let now = Utc::now();
+    let provider = match ticket_provider(app, state) {
+        Ok(provider) => Some(provider),
+        Err(e) => {
+            tracing::debug!("ticket provider unavailable: {}", e);
+            None
+        }
+    };
+    if let Some(provider) = provider.as_ref().filter(|provider| provider.kind() != ProviderKind::Jira) {
+        let mut tickets = Vec::new();
+        for key in keys {
+            if let Some(ticket) = provider.get_ticket(key).await? {
+                tickets.push(CachedTicket { ticket, fetched_at: now, stale: false, missing: false });
+            }
+        }
+        return Ok(tickets);
+    }
+
+    let cached = with_ticket_cache(app, state, |cache| cache.get_many(keys, now))?;
+    let unknown: Vec<&String> = keys
+        .iter()
+        .filter(|key| !cached.iter().any(|cached| &&cached.ticket.key == key))
+        .collect();
+    let Some(provider) = provider.filter(|_| fetch_unknown && !unknown.is_empty()) else {
+        return Ok(cached);
+    };
+
+    let mut fetched = Vec::new();
+    for key in unknown {
+        match provider.get_ticket(key).await {
+            Ok(Some(ticket)) => fetched.push(ticket),
+            Ok(None) => {}
+            // Hors ligne : le reste attendra le prochain rafraîchissement
+            Err(e) => {
+                tracing::debug!(key = %key, "Jira ticket lookup skipped: {}", e);
+                break;
+            }
+        }
+    }
+    if fetched.is_empty() {
+        return Ok(cached);
+    }
+    with_ticket_cache(app, state, |cache| {
+        cache.store(&fetched, now)?;
+        cache.get_many(keys, now)
+    })
+}
+
+// Tickets par clé, depuis le cache ou à défaut depuis Jira
+#[tauri::command]
+async fn get_jira_tickets(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    keys: Vec<String>,
+) -> Result<Vec<CachedTicket>, AppError> {
+    lookup_tickets(&app, &state, &keys, true).await
 }
 
 #[tauri::command]
 async fn get_available_tickets_for_entry(
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
     selected_ticket_keys: Vec<String>,
-) -> Result<Vec<JiraTicketForEntry>, String> {
+) -> Result<Vec<JiraTicketForEntry>, AppError> {
     let available_tickets = {
-        let tickets = state.available_tickets.lock().unwrap();
+        let tickets = state.available_tickets.lock()?;
         tickets.clone()
     };
+    // Les tickets sélectionnés absents de la requête actuelle sont repris du
+    // cache
+    let missing_keys: Vec<String> = selected_ticket_keys
+        .iter()
+        .filter(|key| !available_tickets.iter().any(|t| &t.key == *key))
+        .cloned()
+        .collect();
+    let known_tickets = if missing_keys.is_empty() {
+        Vec::new()
+    } else {
+        lookup_tickets(&app, &state, &missing_keys, true).await?
+    };
     
     let mut result = Vec::new();
     
@@ -150,21 +1127,70 @@ async fn get_available_tickets_for_entry(
     }
     
     // Ajouter ensuite les tickets sélectionnés qui ne sont pas dans la liste actuelle
-    for selected_key in &selected_ticket_keys {
-        if !available_tickets.iter().any(|t| &t.key == selected_key) {
-            result.push(JiraTicketForEntry {
+    for selected_key in &missing_keys {
+        match known_tickets.iter().find(|known| &known.ticket.key == selected_key) {
+            Some(known) => result.push(JiraTicketForEntry {
+                key: selected_key.clone(),
+                summary: known.ticket.fields.summary.clone(),
+                status: known.ticket.fields.status.name.clone(),
+                is_selected: true,
+                is_available: false,
+            }),
+            None => result.push(JiraTicketForEntry {
                 key: selected_key.clone(),
                 summary: "Ticket non trouvé dans la requête actuelle".to_string(),
                 status: "Inconnu".to_string(),
                 is_selected: true,
                 is_available: false,
-            });
+            }),
         }
     }
     
     Ok(result)
 }
 
+// Relit auprès de Jira les tickets du cache devenus anciens, par lots. Hors
+// ligne ou sans Jira configuré, le cache reste tel quel jusqu'au passage
+// suivant.
+const TICKET_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+const TICKET_REFRESH_BATCH: usize = 25;
+
+async fn refresh_stale_tickets(app: &tauri::AppHandle) -> Result<usize, AppError> {
+    let state = app.state::<AppState>();
+    let client = state.jira_client.lock()?.clone().filter(JiraClient::is_configured);
+    let Some(client) = client else {
+        return Ok(0);
+    };
+    let fields = active_profile(app)?
+        .jira
+        .map(|jira| jira.fields)
+        .unwrap_or_default();
+    let client = client.with_fields(fields);
+
+    let keys = with_ticket_cache(app, &state, |cache| cache.stale_keys(Utc::now(), TICKET_REFRESH_BATCH))?;
+    for key in &keys {
+        match client.get_ticket(key).await? {
+            Some(ticket) => with_ticket_cache(app, &state, |cache| cache.store(&[ticket], Utc::now()))?,
+            None => with_ticket_cache(app, &state, |cache| cache.mark_missing(key, Utc::now()))?,
+        }
+    }
+    Ok(keys.len())
+}
+
+fn spawn_ticket_refresh(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TICKET_REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            match refresh_stale_tickets(&app).await {
+                Ok(0) => {}
+                Ok(count) => tracing::debug!(count, "stale Jira tickets refreshed"),
+                Err(e) => tracing::debug!("Jira ticket refresh postponed: {}", e),
+            }
+        }
+    });
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct JiraTicketForEntry {
     pub key: String,
@@ -174,225 +1200,164 @@ pub struct JiraTicketForEntry {
     pub is_available: bool,
 }
 
+// === CATALOGUES (PROJETS, TAGS, TYPES D'ACTIVITÉ) ===
+
+fn catalog_now() -> i64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+// Charge un catalogue, applique `f` puis l'enregistre s'il a changé. Le verrou
+// couvre toute la lecture-écriture : deux créations simultanées ne peuvent
+// pas recevoir le même identifiant.
+fn with_catalog<T>(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    kind: CatalogKind,
+    f: impl FnOnce(&mut Catalog) -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    let _guard = state.catalog_lock.lock()?;
+    let (store, mut catalog) = open_catalog(app, kind)?;
+    let result = f(&mut catalog)?;
+    save_catalog(&store, &catalog)?;
+    Ok(result)
+}
+
+type CatalogStore = std::sync::Arc<tauri_plugin_store::Store<tauri::Wry>>;
+
+// À appeler sous `catalog_lock`
+fn open_catalog(app: &tauri::AppHandle, kind: CatalogKind) -> Result<(CatalogStore, Catalog), AppError> {
+    use tauri_plugin_store::StoreExt;
+    let store = app.store(kind.store_name(&active_profile(app)?))?;
+    let catalog = Catalog::from_store(kind, store.get(kind.key()), store.get(NEXT_ID_KEY), store.get(ALIASES_KEY));
+    Ok((store, catalog))
+}
+
+fn save_catalog(store: &CatalogStore, catalog: &Catalog) -> Result<(), AppError> {
+    if catalog.is_changed() {
+        store.set(catalog.kind().key(), serde_json::to_value(catalog.all())?);
+        store.set(NEXT_ID_KEY, catalog.next_id());
+        store.set(ALIASES_KEY, serde_json::to_value(catalog.aliases())?);
+        store.save()?;
+    }
+    Ok(())
+}
+
+// Reporte un renommage ou une fusion du catalogue dans tout le journal du
+// profil actif. Le catalogue est enregistré avant la réécriture : si elle
+// échoue, les entrées encore sous l'ancien nom restent comptées sous le
+// nouveau grâce à l'alias.
+fn rename_across_journal(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    kind: CatalogKind,
+    dry_run: bool,
+    change: impl FnOnce(&mut Catalog) -> Result<Option<(String, String)>, AppError>,
+) -> Result<EntryRewrite, AppError> {
+    let journal_dir = active_journal_dir(app)?;
+    let _guard = state.catalog_lock.lock()?;
+    let (store, mut catalog) = open_catalog(app, kind)?;
+    let renamed = change(&mut catalog)?;
+    if !dry_run {
+        save_catalog(&store, &catalog)?;
+        // Les alias changent le rapport même si aucun fichier n'est réécrit
+        state.report_cache.lock()?.clear();
+    }
+
+    match renamed {
+        Some((from, to)) => {
+            rewrite_journal_entries(&journal_dir, dry_run, |entry| rename_in_entry(kind, entry, &from, &to)).map_err(AppError::from)
+        }
+        None => Ok(EntryRewrite::default()),
+    }
+}
+
 // === COMMANDES POUR LES PROJETS ===
 
 #[tauri::command]
 async fn get_all_projects(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     include_inactive: Option<bool>,
-) -> Result<Vec<Project>, String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("projects.json").map_err(|e| e.to_string())?;
-    
-    // Charger les projets ou créer des projets par défaut
-    let mut projects: Vec<Project> = match store.get("projects") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|_| {
-            // Projets par défaut
-            vec![
-                Project {
-                    id: Some(1),
-                    name: "Mandate".to_string(),
-                    description: Some("Gestion de mandats CSE".to_string()),
-                    color: "#28a745".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Project {
-                    id: Some(2),
-                    name: "Instance".to_string(),
-                    description: Some("Gestion des réunions".to_string()),
-                    color: "#007bff".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Project {
-                    id: Some(3),
-                    name: "Claims".to_string(),
-                    description: Some("Gestion des réclamations".to_string()),
-                    color: "#ffc107".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-            ]
-        }),
-        None => {
-            // Créer et sauvegarder les projets par défaut
-            let default_projects = vec![
-                Project {
-                    id: Some(1),
-                    name: "Mandate".to_string(),
-                    description: Some("Gestion de mandats CSE".to_string()),
-                    color: "#28a745".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Project {
-                    id: Some(2),
-                    name: "Instance".to_string(),
-                    description: Some("Gestion des réunions".to_string()),
-                    color: "#007bff".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Project {
-                    id: Some(3),
-                    name: "Claims".to_string(),
-                    description: Some("Gestion des réclamations".to_string()),
-                    color: "#ffc107".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-            ];
-            store.set("projects", serde_json::to_value(&default_projects).unwrap());
-            store.save().map_err(|e| e.to_string())?;
-            default_projects
-        }
-    };
-    
-    let include_inactive = include_inactive.unwrap_or(false);
-    if !include_inactive {
-        projects.retain(|p| p.active);
-    }
-    
-    Ok(projects)
+) -> Result<Vec<Project>, AppError> {
+    with_catalog(&app, &state, CatalogKind::Projects, |catalog| Ok(catalog.list(include_inactive.unwrap_or(false))))
 }
 
 #[tauri::command]
 async fn create_project(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     name: String,
     description: Option<String>,
     color: Option<String>,
-) -> Result<Project, String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("projects.json").map_err(|e| e.to_string())?;
-    
-    // Charger les projets existants
-    let mut projects: Vec<Project> = match store.get("projects") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Trouver le prochain ID
-    let next_id = projects.iter().map(|p| p.id.unwrap_or(0)).max().unwrap_or(0) + 1;
-    
-    let new_project = Project {
-        id: Some(next_id),
-        name,
-        description,
-        color: color.unwrap_or("#007bff".to_string()),
-        active: true,
-        created_at: None,
-        updated_at: None,
-    };
-    
-    // Ajouter le nouveau projet
-    projects.push(new_project.clone());
-    
-    // Sauvegarder
-    store.set("projects", serde_json::to_value(&projects).map_err(|e| e.to_string())?);
-    store.save().map_err(|e| e.to_string())?;
-    
-    Ok(new_project)
+) -> Result<Project, AppError> {
+    with_catalog(&app, &state, CatalogKind::Projects, |catalog| catalog.create(&name, description, color, catalog_now()))
 }
 
+// Un changement de nom est reporté dans toutes les entrées du journal ; en
+// simulation (`dry_run`), rien n'est écrit et seul le bilan est renvoyé
 #[tauri::command]
 async fn update_project(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64,
     name: String,
     description: Option<String>,
     color: Option<String>,
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("projects.json").map_err(|e| e.to_string())?;
-    
-    // Charger les projets existants
-    let mut projects: Vec<Project> = match store.get("projects") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Trouver et mettre à jour le projet
-    if let Some(project) = projects.iter_mut().find(|p| p.id == Some(id)) {
-        project.name = name;
-        project.description = description;
-        if let Some(color) = color {
-            project.color = color;
-        }
-        project.updated_at = Some((std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64).to_string());
+    dry_run: bool,
+) -> Result<EntryRewrite, AppError> {
+    rename_across_journal(&app, &state, CatalogKind::Projects, dry_run, |catalog| catalog.update(id, &name, description, color, catalog_now()))
+}
+
+// Renomme le projet dans le catalogue et dans toutes les entrées du journal
+#[tauri::command]
+async fn rename_project(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: i64,
+    name: String,
+    dry_run: bool,
+) -> Result<EntryRewrite, AppError> {
+    rename_across_journal(&app, &state, CatalogKind::Projects, dry_run, |catalog| catalog.rename(id, &name, catalog_now()).map(Some))
+}
+
+// Fusionne `source_id` dans `target_id` : le premier disparaît du catalogue
+// et ses entrées passent au second
+#[tauri::command]
+async fn merge_projects(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    source_id: i64,
+    target_id: i64,
+    dry_run: bool,
+) -> Result<EntryRewrite, AppError> {
+    rename_across_journal(&app, &state, CatalogKind::Projects, dry_run, |catalog| catalog.merge(source_id, target_id, catalog_now()).map(Some))
+}
 
-        // Sauvegarder
-        store.set("projects", serde_json::to_value(&projects).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        
-        Ok(())
-    } else {
-        Err("Projet non trouvé".to_string())
-    }
+// Anciens noms (en minuscules) et nom actuel correspondant
+#[tauri::command]
+async fn get_project_aliases(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<BTreeMap<String, String>, AppError> {
+    with_catalog(&app, &state, CatalogKind::Projects, |catalog| Ok(catalog.aliases().clone()))
 }
 
 #[tauri::command]
 async fn delete_project(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("projects.json").map_err(|e| e.to_string())?;
-    
-    // Charger les projets existants
-    let mut projects: Vec<Project> = match store.get("projects") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Supprimer le projet
-    let initial_len = projects.len();
-    projects.retain(|p| p.id != Some(id));
-    
-    if projects.len() < initial_len {
-        // Sauvegarder
-        store.set("projects", serde_json::to_value(&projects).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Projet non trouvé".to_string())
-    }
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::Projects, |catalog| catalog.delete(id))
 }
 
 #[tauri::command]
 async fn toggle_project_status(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("projects.json").map_err(|e| e.to_string())?;
-    
-    // Charger les projets existants
-    let mut projects: Vec<Project> = match store.get("projects") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Trouver et basculer le statut du projet
-    if let Some(project) = projects.iter_mut().find(|p| p.id == Some(id)) {
-        project.active = !project.active;
-        project.updated_at = Some((std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64).to_string());
-
-        // Sauvegarder
-        store.set("projects", serde_json::to_value(&projects).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        
-        Ok(())
-    } else {
-        Err("Projet non trouvé".to_string())
-    }
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::Projects, |catalog| catalog.toggle(id, catalog_now()))
 }
 
 // === COMMANDES POUR LES TAGS ===
@@ -400,297 +1365,88 @@ async fn toggle_project_status(
 #[tauri::command]
 async fn get_all_tags(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     include_inactive: Option<bool>,
-) -> Result<Vec<Tag>, String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("tags.json").map_err(|e| e.to_string())?;
-    
-    // Charger les tags ou créer des tags par défaut
-    let mut tags: Vec<Tag> = match store.get("tags") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|_| {
-            // Tags par défaut
-            vec![
-                Tag {
-                    id: Some(1),
-                    name: "bug".to_string(),
-                    description: Some("Correction de bugs".to_string()),
-                    color: "#dc3545".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Tag {
-                    id: Some(2),
-                    name: "feature".to_string(),
-                    description: Some("Nouvelle fonctionnalité".to_string()),
-                    color: "#28a745".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Tag {
-                    id: Some(3),
-                    name: "documentation".to_string(),
-                    description: Some("Documentation".to_string()),
-                    color: "#17a2b8".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-            ]
-        }),
-        None => {
-            // Créer et sauvegarder les tags par défaut
-            let default_tags = vec![
-                Tag {
-                    id: Some(1),
-                    name: "bug".to_string(),
-                    description: Some("Correction de bugs".to_string()),
-                    color: "#dc3545".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Tag {
-                    id: Some(2),
-                    name: "feature".to_string(),
-                    description: Some("Nouvelle fonctionnalité".to_string()),
-                    color: "#28a745".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-                Tag {
-                    id: Some(3),
-                    name: "documentation".to_string(),
-                    description: Some("Documentation".to_string()),
-                    color: "#17a2b8".to_string(),
-                    active: true,
-                    created_at: None,
-                    updated_at: None,
-                },
-            ];
-            store.set("tags", serde_json::to_value(&default_tags).unwrap());
-            store.save().map_err(|e| e.to_string())?;
-            default_tags
-        }
-    };
-    
-    let include_inactive = include_inactive.unwrap_or(false);
-    if !include_inactive {
-        tags.retain(|t| t.active);
-    }
-    
-    Ok(tags)
+) -> Result<Vec<Tag>, AppError> {
+    with_catalog(&app, &state, CatalogKind::Tags, |catalog| Ok(catalog.list(include_inactive.unwrap_or(false))))
 }
 
 #[tauri::command]
 async fn create_tag(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     name: String,
     description: Option<String>,
     color: Option<String>,
-) -> Result<Tag, String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("tags.json").map_err(|e| e.to_string())?;
-    
-    // Charger les tags existants
-    let mut tags: Vec<Tag> = match store.get("tags") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Trouver le prochain ID
-    let next_id = tags.iter().map(|t| t.id.unwrap_or(0)).max().unwrap_or(0) + 1;
-    
-    let new_tag = Tag {
-        id: Some(next_id),
-        name,
-        description,
-        color: color.unwrap_or("#6c757d".to_string()),
-        active: true,
-        created_at: None,
-        updated_at: None,
-    };
-    
-    // Ajouter le nouveau tag
-    tags.push(new_tag.clone());
-    
-    // Sauvegarder
-    store.set("tags", serde_json::to_value(&tags).map_err(|e| e.to_string())?);
-    store.save().map_err(|e| e.to_string())?;
-    
-    Ok(new_tag)
+) -> Result<Tag, AppError> {
+    with_catalog(&app, &state, CatalogKind::Tags, |catalog| catalog.create(&name, description, color, catalog_now()))
 }
 
+// Un changement de nom est reporté dans toutes les entrées du journal ; en
+// simulation (`dry_run`), rien n'est écrit et seul le bilan est renvoyé
 #[tauri::command]
 async fn update_tag(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64,
     name: String,
     description: Option<String>,
     color: Option<String>,
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("tags.json").map_err(|e| e.to_string())?;
-    
-    // Charger les tags existants
-    let mut tags: Vec<Tag> = match store.get("tags") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Trouver et mettre à jour le tag
-    if let Some(tag) = tags.iter_mut().find(|t| t.id == Some(id)) {
-        tag.name = name;
-        tag.description = description;
-        if let Some(color) = color {
-            tag.color = color;
-        }
-        tag.updated_at = Some((std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64).to_string());
+    dry_run: bool,
+) -> Result<EntryRewrite, AppError> {
+    rename_across_journal(&app, &state, CatalogKind::Tags, dry_run, |catalog| catalog.update(id, &name, description, color, catalog_now()))
+}
 
-        // Sauvegarder
-        store.set("tags", serde_json::to_value(&tags).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        
-        Ok(())
-    } else {
-        Err("Tag non trouvé".to_string())
-    }
+// Renomme le tag dans le catalogue et dans toutes les entrées du journal
+#[tauri::command]
+async fn rename_tag(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: i64,
+    name: String,
+    dry_run: bool,
+) -> Result<EntryRewrite, AppError> {
+    rename_across_journal(&app, &state, CatalogKind::Tags, dry_run, |catalog| catalog.rename(id, &name, catalog_now()).map(Some))
+}
+
+// Fusionne `source_id` dans `target_id` : le premier disparaît du catalogue
+// et ses entrées passent au second
+#[tauri::command]
+async fn merge_tags(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    source_id: i64,
+    target_id: i64,
+    dry_run: bool,
+) -> Result<EntryRewrite, AppError> {
+    rename_across_journal(&app, &state, CatalogKind::Tags, dry_run, |catalog| catalog.merge(source_id, target_id, catalog_now()).map(Some))
+}
+
+// Anciens noms (en minuscules) et nom actuel correspondant
+#[tauri::command]
+async fn get_tag_aliases(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<BTreeMap<String, String>, AppError> {
+    with_catalog(&app, &state, CatalogKind::Tags, |catalog| Ok(catalog.aliases().clone()))
 }
 
 #[tauri::command]
 async fn delete_tag(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("tags.json").map_err(|e| e.to_string())?;
-    
-    // Charger les tags existants
-    let mut tags: Vec<Tag> = match store.get("tags") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Supprimer le tag
-    let initial_len = tags.len();
-    tags.retain(|t| t.id != Some(id));
-    
-    if tags.len() < initial_len {
-        // Sauvegarder
-        store.set("tags", serde_json::to_value(&tags).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Tag non trouvé".to_string())
-    }
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::Tags, |catalog| catalog.delete(id))
 }
 
 #[tauri::command]
 async fn toggle_tag_status(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("tags.json").map_err(|e| e.to_string())?;
-    
-    // Charger les tags existants
-    let mut tags: Vec<Tag> = match store.get("tags") {
-        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
-        None => Vec::new(),
-    };
-    
-    // Trouver et basculer le statut du tag
-    if let Some(tag) = tags.iter_mut().find(|t| t.id == Some(id)) {
-        tag.active = !tag.active;
-        tag.updated_at = Some((std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64).to_string());
-
-        // Sauvegarder
-        store.set("tags", serde_json::to_value(&tags).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        
-        Ok(())
-    } else {
-        Err("Tag non trouvé".to_string())
-    }
-}
-
-fn default_activity_types_catalog() -> Vec<ActivityType> {
-    vec![
-        ActivityType {
-            id: Some(1),
-            name: "debug".to_string(),
-            description: Some("Investigation et correction de probleme".to_string()),
-            color: "#dc3545".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(2),
-            name: "développement".to_string(),
-            description: Some("Implementation de fonctionnalites".to_string()),
-            color: "#007bff".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(3),
-            name: "documentation".to_string(),
-            description: Some("Redaction et mise a jour de documentation".to_string()),
-            color: "#17a2b8".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(4),
-            name: "formation".to_string(),
-            description: Some("Apprentissage et montee en competence".to_string()),
-            color: "#6f42c1".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(5),
-            name: "infrastructure".to_string(),
-            description: Some("Outillage, CI, environnements et operations".to_string()),
-            color: "#6c757d".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(6),
-            name: "réunion".to_string(),
-            description: Some("Synchronisation, atelier ou point d'equipe".to_string()),
-            color: "#ffc107".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(7),
-            name: "revue de code".to_string(),
-            description: Some("Lecture et validation de modifications".to_string()),
-            color: "#28a745".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-        ActivityType {
-            id: Some(8),
-            name: "veille technologique".to_string(),
-            description: Some("Exploration et suivi technique".to_string()),
-            color: "#fd7e14".to_string(),
-            active: true,
-            created_at: None,
-            updated_at: None,
-        },
-    ]
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::Tags, |catalog| catalog.toggle(id, catalog_now()))
 }
 
 // === COMMANDES POUR LES TYPES D'ACTIVITÉ ===
@@ -698,427 +1454,104 @@ fn default_activity_types_catalog() -> Vec<ActivityType> {
 #[tauri::command]
 async fn get_all_activity_types(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     include_inactive: Option<bool>,
-) -> Result<Vec<ActivityType>, String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("activity-types.json").map_err(|e| e.to_string())?;
-    let default_activity_types = default_activity_types_catalog();
-
-    let mut activity_types: Vec<ActivityType> = match store.get("activity_types") {
-        Some(value) => match serde_json::from_value(value.clone()) {
-            Ok(activity_types) => activity_types,
-            Err(_) => default_activity_types.clone(),
-        },
-        None => {
-            store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-            store.save().map_err(|e| e.to_string())?;
-            default_activity_types.clone()
-        }
-    };
-
-    if !include_inactive.unwrap_or(false) {
-        activity_types.retain(|activity_type| activity_type.active);
-    }
-
-    Ok(activity_types)
+) -> Result<Vec<ActivityType>, AppError> {
+    with_catalog(&app, &state, CatalogKind::ActivityTypes, |catalog| Ok(catalog.list(include_inactive.unwrap_or(false))))
 }
 
 #[tauri::command]
 async fn create_activity_type(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     name: String,
     description: Option<String>,
     color: Option<String>,
-) -> Result<ActivityType, String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("activity-types.json").map_err(|e| e.to_string())?;
-    let default_activity_types = default_activity_types_catalog();
-
-    let mut activity_types: Vec<ActivityType> = match store.get("activity_types") {
-        Some(value) => match serde_json::from_value(value.clone()) {
-            Ok(activity_types) => activity_types,
-            Err(_) => {
-                store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-                store.save().map_err(|e| e.to_string())?;
-                default_activity_types.clone()
-            }
-        },
-        None => {
-            store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-            store.save().map_err(|e| e.to_string())?;
-            default_activity_types.clone()
-        }
-    };
-
-    if activity_types.iter().any(|activity_type| activity_type.name == name) {
-        return Err("Un type d'activité avec ce nom existe déjà".to_string());
-    }
-
-    let next_id = activity_types.iter().map(|activity_type| activity_type.id.unwrap_or(0)).max().unwrap_or(0) + 1;
-
-    let new_activity_type = ActivityType {
-        id: Some(next_id),
-        name,
-        description,
-        color: color.unwrap_or("#6c757d".to_string()),
-        active: true,
-        created_at: None,
-        updated_at: None,
-    };
-
-    activity_types.push(new_activity_type.clone());
-    store.set("activity_types", serde_json::to_value(&activity_types).map_err(|e| e.to_string())?);
-    store.save().map_err(|e| e.to_string())?;
-
-    Ok(new_activity_type)
+) -> Result<ActivityType, AppError> {
+    with_catalog(&app, &state, CatalogKind::ActivityTypes, |catalog| catalog.create(&name, description, color, catalog_now()))
 }
 
 #[tauri::command]
 async fn update_activity_type(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64,
     name: String,
     description: Option<String>,
     color: Option<String>,
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("activity-types.json").map_err(|e| e.to_string())?;
-    let default_activity_types = default_activity_types_catalog();
-
-    let mut activity_types: Vec<ActivityType> = match store.get("activity_types") {
-        Some(value) => match serde_json::from_value(value.clone()) {
-            Ok(activity_types) => activity_types,
-            Err(_) => {
-                store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-                store.save().map_err(|e| e.to_string())?;
-                default_activity_types.clone()
-            }
-        },
-        None => {
-            store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-            store.save().map_err(|e| e.to_string())?;
-            default_activity_types.clone()
-        }
-    };
-
-    if activity_types.iter().any(|activity_type| activity_type.id != Some(id) && activity_type.name == name) {
-        return Err("Un type d'activité avec ce nom existe déjà".to_string());
-    }
-
-    if let Some(activity_type) = activity_types.iter_mut().find(|activity_type| activity_type.id == Some(id)) {
-        activity_type.name = name;
-        activity_type.description = description;
-        if let Some(color) = color {
-            activity_type.color = color;
-        }
-        activity_type.updated_at = Some((std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64).to_string());
-
-        store.set("activity_types", serde_json::to_value(&activity_types).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-
-        Ok(())
-    } else {
-        Err("Type d'activité non trouvé".to_string())
-    }
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::ActivityTypes, |catalog| catalog.update(id, &name, description, color, catalog_now()).map(|_| ()))
 }
 
 #[tauri::command]
 async fn delete_activity_type(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("activity-types.json").map_err(|e| e.to_string())?;
-    let default_activity_types = default_activity_types_catalog();
-
-    let mut activity_types: Vec<ActivityType> = match store.get("activity_types") {
-        Some(value) => match serde_json::from_value(value.clone()) {
-            Ok(activity_types) => activity_types,
-            Err(_) => {
-                store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-                store.save().map_err(|e| e.to_string())?;
-                default_activity_types.clone()
-            }
-        },
-        None => {
-            store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-            store.save().map_err(|e| e.to_string())?;
-            default_activity_types.clone()
-        }
-    };
-
-    let initial_len = activity_types.len();
-    activity_types.retain(|activity_type| activity_type.id != Some(id));
-
-    if activity_types.len() < initial_len {
-        store.set("activity_types", serde_json::to_value(&activity_types).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Type d'activité non trouvé".to_string())
-    }
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::ActivityTypes, |catalog| catalog.delete(id))
 }
 
 #[tauri::command]
 async fn toggle_activity_type_status(
     app: tauri::AppHandle,
+    state: State<'_, AppState>,
     id: i64
-) -> Result<(), String> {
-    use tauri_plugin_store::StoreExt;
-    let store = app.store("activity-types.json").map_err(|e| e.to_string())?;
-    let default_activity_types = default_activity_types_catalog();
-
-    let mut activity_types: Vec<ActivityType> = match store.get("activity_types") {
-        Some(value) => match serde_json::from_value(value.clone()) {
-            Ok(activity_types) => activity_types,
-            Err(_) => {
-                store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-                store.save().map_err(|e| e.to_string())?;
-                default_activity_types.clone()
-            }
-        },
-        None => {
-            store.set("activity_types", serde_json::to_value(&default_activity_types).map_err(|e| e.to_string())?);
-            store.save().map_err(|e| e.to_string())?;
-            default_activity_types.clone()
-        }
-    };
-
-    if let Some(activity_type) = activity_types.iter_mut().find(|activity_type| activity_type.id == Some(id)) {
-        activity_type.active = !activity_type.active;
-        activity_type.updated_at = Some((std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64).to_string());
-
-        store.set("activity_types", serde_json::to_value(&activity_types).map_err(|e| e.to_string())?);
-        store.save().map_err(|e| e.to_string())?;
-
-        Ok(())
-    } else {
-        Err("Type d'activité non trouvé".to_string())
-    }
+) -> Result<(), AppError> {
+    with_catalog(&app, &state, CatalogKind::ActivityTypes, |catalog| catalog.toggle(id, catalog_now()))
 }
 
 // === COMMANDES POUR LES RAPPORTS D'ACTIVITÉ ===
 
-#[derive(Debug, serde::Serialize)]
-struct ActivityReport {
-    period_start: String,
-    period_end: String,
-    total_entries: usize,
-    total_hours: f64,
-    projects_summary: Vec<ProjectSummary>,
-    tags_summary: Vec<TagSummary>,
-    activity_types: HashMap<String, usize>,
-    daily_breakdown: HashMap<String, f64>,
-    monthly_breakdown: HashMap<String, f64>,
-    monthly_details: Vec<MonthlyDetail>,
-}
-
-#[derive(Debug, serde::Serialize)]
-struct ProjectSummary {
-    name: String,
-    entries: usize,
-    hours: f64,
-    color: String,
-}
-
-#[derive(Debug, serde::Serialize)]
-struct TagSummary {
-    name: String,
-    count: usize,
-    color: String,
-}
-
-#[derive(Debug, serde::Serialize)]
-struct MonthlyDetail {
-    month: String,
-    hours: f64,
-    projects: Vec<String>,
-    tags: Vec<String>,
-    project_hours: HashMap<String, f64>,
-    tag_hours: HashMap<String, f64>,
-}
-
 #[tauri::command]
 async fn generate_activity_report(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
     start_date: String,
     end_date: String,
-) -> Result<ActivityReport, String> {
-    let dates = get_available_journal_dates().map_err(|e| e.to_string())?;
-    
-    // Filtrer les dates dans la plage
-    let filtered_dates: Vec<String> = dates
-        .into_iter()
-        .filter(|date| date >= &start_date && date <= &end_date)
-        .collect();
-    
-    let mut all_entries = Vec::new();
-    
-    // Charger toutes les entrées de la période
-    for date in &filtered_dates {
-        match load_journal_file(date) {
-            Ok(content) => {
-                if !content.is_empty() {
-                    let entries = parse_journal_entries(&content);
-                    for mut entry in entries {
-                        entry.timestamp = format!("{} {}", date, entry.timestamp);
-                        all_entries.push(entry);
-                    }
-                }
-            }
-            Err(_) => continue,
-        }
-    }
-    
-    // Calculer les statistiques
-    let total_entries = all_entries.len();
-    let mut total_hours = 0.0;
-    let mut projects_map: HashMap<String, (usize, f64, String)> = HashMap::new();
-    let mut tags_map: HashMap<String, usize> = HashMap::new();
-    let mut activity_types: HashMap<String, usize> = HashMap::new();
-    let mut daily_breakdown: HashMap<String, f64> = HashMap::new();
-    let mut monthly_breakdown: HashMap<String, f64> = HashMap::new();
-    let mut monthly_projects: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
-    let mut monthly_tags: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
-    let mut monthly_project_hours: HashMap<String, HashMap<String, f64>> = HashMap::new();
-    let mut monthly_tag_hours: HashMap<String, HashMap<String, f64>> = HashMap::new();
-    
-    for entry in &all_entries {
-        // Parseage de la durée (format "3h", "2h30", "1.5h")
-        let hours = parse_duration(&entry.duration);
-        total_hours += hours;
-        
-        // Projets
-        let project_entry = projects_map.entry(entry.project.clone()).or_insert((0, 0.0, "#007bff".to_string()));
-        project_entry.0 += 1;
-        project_entry.1 += hours;
-        
-        // Tags
-        for tag in &entry.tags {
-            *tags_map.entry(tag.clone()).or_insert(0) += 1;
-        }
-        
-        // Types d'activité
-        *activity_types.entry(entry.entry_type.clone()).or_insert(0) += 1;
-        
-        // Décomposition par jour
-        let date = entry.timestamp.split(' ').next().unwrap_or("").to_string();
-        *daily_breakdown.entry(date.clone()).or_insert(0.0) += hours;
-        
-        // Décomposition par mois (format YYYY-MM)
-        if let Some(month) = date.get(0..7) { // Prendre les 7 premiers caractères (YYYY-MM)
-            *monthly_breakdown.entry(month.to_string()).or_insert(0.0) += hours;
-            
-            // Collecter les projets pour ce mois
-            monthly_projects.entry(month.to_string()).or_insert_with(std::collections::HashSet::new).insert(entry.project.clone());
-            
-            // Collecter les heures par projet pour ce mois
-            let month_project_hours = monthly_project_hours.entry(month.to_string()).or_insert_with(HashMap::new);
-            *month_project_hours.entry(entry.project.clone()).or_insert(0.0) += hours;
-            
-            // Collecter les tags pour ce mois
-            let monthly_tag_set = monthly_tags.entry(month.to_string()).or_insert_with(std::collections::HashSet::new);
-            for tag in &entry.tags {
-                monthly_tag_set.insert(tag.clone());
-            }
-            
-            // Collecter les heures par tag pour ce mois
-            let month_tag_hours = monthly_tag_hours.entry(month.to_string()).or_insert_with(HashMap::new);
-            for tag in &entry.tags {
-                *month_tag_hours.entry(tag.clone()).or_insert(0.0) += hours;
-            }
-        }
-    }
-    
-    // Convertir en structures de résultat
-    let projects_summary: Vec<ProjectSummary> = projects_map
-        .into_iter()
-        .map(|(name, (entries, hours, color))| ProjectSummary {
-            name,
-            entries,
-            hours,
-            color,
-        })
-        .collect();
-    
-    let tags_summary: Vec<TagSummary> = tags_map
-        .into_iter()
-        .map(|(name, count)| TagSummary {
-            name,
-            count,
-            color: "#6c757d".to_string(), // Couleur par défaut
-        })
-        .collect();
-    
-    // Générer les détails mensuels
-    let mut monthly_details: Vec<MonthlyDetail> = monthly_breakdown
-        .iter()
-        .map(|(month, hours)| {
-            let projects = monthly_projects.get(month)
-                .map(|set| {
-                    let mut vec: Vec<String> = set.iter().cloned().collect();
-                    vec.sort();
-                    vec
-                })
-                .unwrap_or_default();
-            
-            let tags = monthly_tags.get(month)
-                .map(|set| {
-                    let mut vec: Vec<String> = set.iter().cloned().collect();
-                    vec.sort();
-                    vec
-                })
-                .unwrap_or_default();
-            
-            let project_hours = monthly_project_hours.get(month)
-                .cloned()
-                .unwrap_or_default();
-            
-            let tag_hours = monthly_tag_hours.get(month)
-                .cloned()
-                .unwrap_or_default();
-            
-            MonthlyDetail {
-                month: month.clone(),
-                hours: *hours,
-                projects,
-                tags,
-                project_hours,
-                tag_hours,
-            }
+) -> Result<ActivityReport, AppError> {
+    activity_report(&app, &state, &start_date, &end_date)
+}
+
+// Rapport de la période, sommé à partir des agrégats journaliers de l'index.
+// Le dernier rapport est gardé en cache tant que le journal ne change pas.
+fn activity_report(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    start_date: &str,
+    end_date: &str,
+) -> Result<ActivityReport, AppError> {
+    let project_aliases = with_catalog(app, state, CatalogKind::Projects, |catalog| Ok(catalog.aliases().clone()))?;
+    let tag_aliases = with_catalog(app, state, CatalogKind::Tags, |catalog| Ok(catalog.aliases().clone()))?;
+    with_journal_index(app, state, |index| {
+        let mut cache = state
+            .report_cache
+            .lock()
+            .map_err(AppError::from)?;
+        cache.get_or_build(index.generation(), start_date, end_date, || {
+            let mut days = index.day_aggregates_between(start_date, end_date)?;
+            apply_aliases(&mut days, &project_aliases, &tag_aliases);
+            Ok(build_activity_report(start_date, end_date, &days))
         })
-        .collect();
-    
-    // Trier par mois
-    monthly_details.sort_by(|a, b| a.month.cmp(&b.month));
-    
-    Ok(ActivityReport {
-        period_start: start_date,
-        period_end: end_date,
-        total_entries,
-        total_hours,
-        projects_summary,
-        tags_summary,
-        activity_types,
-        daily_breakdown,
-        monthly_breakdown,
-        monthly_details,
     })
 }
 
 #[tauri::command]
 async fn export_activity_report_to_docx(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
     start_date: String,
     end_date: String,
     file_path: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // Générer le rapport d'activité
-    let report = generate_activity_report(start_date.clone(), end_date.clone()).await?;
+    let report = activity_report(&app, &state, &start_date, &end_date)?;
     
     // Créer un nouveau document DOCX
     let doc = Docx::new()
         .add_paragraph(
             Paragraph::new()
-                .add_run(Run::new().add_text(&format!("Rapport d'Activité - {} au {}", start_date, end_date)).bold())
+                .add_run(Run::new().add_text(format!("Rapport d'Activité - {} au {}", start_date, end_date)).bold())
                 .style("Title")
         )
         .add_paragraph(
@@ -1132,16 +1565,16 @@ async fn export_activity_report_to_docx(
         )
         .add_paragraph(
             Paragraph::new()
-                .add_run(Run::new().add_text(&format!("• Total d'entrées: {}", report.total_entries)))
+                .add_run(Run::new().add_text(format!("• Total d'entrées: {}", report.total_entries)))
         )
         .add_paragraph(
             Paragraph::new()
-                .add_run(Run::new().add_text(&format!("• Total d'heures: {:.1}h", report.total_hours)))
+                .add_run(Run::new().add_text(format!("• Total d'heures: {:.1}h", report.total_hours)))
         )
         .add_paragraph(
             Paragraph::new()
-                .add_run(Run::new().add_text(&format!("• Moyenne par jour: {:.1}h", 
-                    if report.daily_breakdown.len() > 0 { 
+                .add_run(Run::new().add_text(format!("• Moyenne par jour: {:.1}h", 
+                    if !report.daily_breakdown.is_empty() { 
                         report.total_hours / report.daily_breakdown.len() as f64 
                     } else { 
                         0.0 
@@ -1166,7 +1599,7 @@ async fn export_activity_report_to_docx(
             let percentage = (project.hours / report.total_hours) * 100.0;
             doc = doc.add_paragraph(
                 Paragraph::new()
-                    .add_run(Run::new().add_text(&format!("• {}: {} entrées, {:.1}h ({:.1}%)", 
+                    .add_run(Run::new().add_text(format!("• {}: {} entrées, {:.1}h ({:.1}%)", 
                         project.name, project.entries, project.hours, percentage)))
             );
         }
@@ -1192,7 +1625,7 @@ async fn export_activity_report_to_docx(
                 // Titre du mois avec total d'heures
                 doc = doc.add_paragraph(
                     Paragraph::new()
-                        .add_run(Run::new().add_text(&format!("• {}: {:.1}h", formatted_month, month_detail.hours)).bold())
+                        .add_run(Run::new().add_text(format!("• {}: {:.1}h", formatted_month, month_detail.hours)).bold())
                 );
                 
                 // Détail par projets
@@ -1208,7 +1641,7 @@ async fn export_activity_report_to_docx(
                     for (project_name, hours) in project_sorted {
                         doc = doc.add_paragraph(
                             Paragraph::new()
-                                .add_run(Run::new().add_text(&format!("    - {}: {:.1}h", project_name, hours)))
+                                .add_run(Run::new().add_text(format!("    - {}: {:.1}h", project_name, hours)))
                         );
                     }
                 }
@@ -1226,7 +1659,7 @@ async fn export_activity_report_to_docx(
                     for (tag_name, hours) in tag_sorted {
                         doc = doc.add_paragraph(
                             Paragraph::new()
-                                .add_run(Run::new().add_text(&format!("    - #{}: {:.1}h", tag_name, hours)))
+                                .add_run(Run::new().add_text(format!("    - #{}: {:.1}h", tag_name, hours)))
                         );
                     }
                 }
@@ -1250,7 +1683,28 @@ async fn export_activity_report_to_docx(
         for (activity_type, count) in &report.activity_types {
             doc = doc.add_paragraph(
                 Paragraph::new()
-                    .add_run(Run::new().add_text(&format!("• {}: {} fois", activity_type, count)))
+                    .add_run(Run::new().add_text(format!("• {}: {} fois", activity_type, count)))
+            );
+        }
+        
+        doc = doc.add_paragraph(
+            Paragraph::new()
+                .add_run(Run::new().add_text(""))
+        );
+    }
+
+    // Durées non comptées
+    if !report.duration_issues.is_empty() {
+        doc = doc.add_paragraph(
+            Paragraph::new()
+                .add_run(Run::new().add_text("Durées non comptées").bold().size(28))
+        );
+        
+        for issue in &report.duration_issues {
+            doc = doc.add_paragraph(
+                Paragraph::new()
+                    .add_run(Run::new().add_text(format!("• {} {}: « {} » ({})", 
+                        issue.date, issue.timestamp, issue.value, issue.reason)))
             );
         }
         
@@ -1270,75 +1724,18 @@ async fn export_activity_report_to_docx(
         for tag in &report.tags_summary {
             doc = doc.add_paragraph(
                 Paragraph::new()
-                    .add_run(Run::new().add_text(&format!("• {}: {} fois", tag.name, tag.count)))
+                    .add_run(Run::new().add_text(format!("• {}: {} fois", tag.name, tag.count)))
             );
         }
     }
 
     // Sauvegarder le document
-    let file = std::fs::File::create(&file_path).map_err(|e| e.to_string())?;
-    doc.build().pack(file).map_err(|e| e.to_string())?;
+    let file = std::fs::File::create(&file_path)?;
+    doc.build().pack(file).map_err(|e| AppError::new(ErrorCode::Io, e.to_string()))?;
     
     Ok(format!("Rapport exporté vers: {}", file_path))
 }
 
-fn parse_duration(duration: &str) -> f64 {
-    let duration = duration.trim().to_lowercase();
-    let mut total_minutes = 0.0;
-    
-    // Gérer différents formats de durée et tout convertir en minutes
-    if duration.contains("h") {
-        // Formats avec heures: "3h", "2.5h", "2h30", "2h30min", etc.
-        if let Some((hours_part, rest)) = duration.split_once('h') {
-            // Parser les heures
-            if let Ok(hours) = hours_part.parse::<f64>() {
-                total_minutes += hours * 60.0;
-            }
-            
-            // Parser les minutes restantes si présentes
-            if !rest.is_empty() {
-                let minutes_str = rest.trim()
-                    .replace("min", "")
-                    .replace("minute", "")
-                    .replace("minutes", "")
-                    .trim()
-                    .to_string();
-                    
-                if let Ok(minutes) = minutes_str.parse::<f64>() {
-                    total_minutes += minutes;
-                }
-            }
-        }
-    } else if duration.contains("min") {
-        // Format purement en minutes: "30min", "45 minutes"
-        let minutes_str = duration
-            .replace("min", "")
-            .replace("minute", "")
-            .replace("minutes", "")
-            .trim()
-            .to_string();
-            
-        if let Ok(minutes) = minutes_str.parse::<f64>() {
-            total_minutes = minutes;
-        }
-    } else {
-        // Format numérique pur: "30", "1.5", etc.
-        // Assumer que c'est en minutes si < 10, sinon en heures décimales
-        if let Ok(value) = duration.parse::<f64>() {
-            if value < 10.0 {
-                // Probablement des heures (ex: "2", "3.5")
-                total_minutes = value * 60.0;
-            } else {
-                // Probablement des minutes (ex: "30", "45")
-                total_minutes = value;
-            }
-        }
-    }
-    
-    // Retourner en heures (pour compatibilité avec le reste du code)
-    total_minutes / 60.0
-}
-
 // === COMMANDES POUR LES PRÉFÉRENCES (Version simple avec Store temporaire) ===
 
 use tauri_plugin_store::StoreExt;
@@ -1347,8 +1744,8 @@ use tauri_plugin_store::StoreExt;
 async fn get_preference(
     app: tauri::AppHandle,
     key: String,
-) -> Result<Option<String>, String> {
-    let store = app.store("store.json").map_err(|e| e.to_string())?;
+) -> Result<Option<String>, AppError> {
+    let store = app.store("store.json")?;
     match store.get(&key) {
         Some(value) => {
             if let Some(s) = value.as_str() {
@@ -1366,19 +1763,72 @@ async fn set_preference(
     app: tauri::AppHandle,
     key: String,
     value: String,
-) -> Result<(), String> {
-    let store = app.store("store.json").map_err(|e| e.to_string())?;
+) -> Result<(), AppError> {
+    // Filtre de logs et langue s'appliquent tout de suite ; un filtre invalide
+    // est refusé
+    if key == LOG_FILTER_PREFERENCE {
+        logging::set_filter(&value)?;
+    }
+    if key == LOCALE_PREFERENCE {
+        set_locale(&value);
+    }
+    if key == TICKET_PROVIDER_PREFERENCE {
+        ProviderKind::from_preference(Some(&value))?;
+    }
+    let store = app.store("store.json")?;
     store.set(&key, serde_json::Value::String(value));
-    store.save().map_err(|e| e.to_string())?;
+    store.save()?;
     Ok(())
 }
 
+// Archive zip à joindre à un signalement : version, système, configuration du
+// profil actif et logs récents, emails et secrets masqués
+#[tauri::command]
+async fn export_diagnostics(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    destination: String,
+) -> Result<String, AppError> {
+    let profile = active_profile(&app)?;
+    let jira_connected = state.jira_client.lock()?.is_some();
+    let store = app.store("store.json")?;
+    let log_filter = store
+        .get(LOG_FILTER_PREFERENCE)
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_LOG_FILTER.to_string());
+
+    let mut report = vec![
+        format!("Application : {} {}", app.package_info().name, app.package_info().version),
+        format!("Système : {} {}", std::env::consts::OS, std::env::consts::ARCH),
+        format!("Généré le : {}", chrono::Local::now().to_rfc3339()),
+        format!("Filtre de logs : {}", log_filter),
+        format!("Profil : {} ({})", profile.name, profile.id),
+        format!("Dossier du journal : {}", profile.root_dir),
+        format!("Jira connecté : {}", if jira_connected { "oui" } else { "non" }),
+    ];
+    if let Some(jira) = &profile.jira {
+        report.push(format!("Jira : {} ({:?}, {:?})", jira.base_url, jira.deployment, jira.auth_method));
+        report.push(format!("Champs Jira : {:?}", jira.fields.fields));
+    }
+
+    let log_dir = logging::log_dir()
+        .unwrap_or(app.path().app_data_dir()?.join("logs"));
+    let destination = std::path::PathBuf::from(destination);
+    logging::write_diagnostic_bundle(&log_dir, &destination, &report.join("\n"))?;
+    tracing::info!(path = %destination.display(), "diagnostic bundle exported");
+    Ok(destination.to_string_lossy().to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .manage(AppState {
             jira_client: Mutex::new(None),
             available_tickets: Mutex::new(Vec::new()),
+            journal_index: Mutex::new(None),
+            ticket_cache: Mutex::new(None),
+            report_cache: Mutex::new(ReportCache::default()),
+            catalog_lock: Mutex::new(()),
         })
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::default().build())
@@ -1394,18 +1844,46 @@ pub fn run() {
             parse_journal_entries_cmd,
             update_journal_entry_cmd,
             delete_journal_entry_cmd,
+            restore_journal_backup_cmd,
+            migrate_durations,
+            rewrite_jira_links,
+            validate_day,
+            search_journal,
+            list_profiles,
+            get_active_profile,
+            create_profile,
+            update_profile,
+            switch_profile,
             initialize_jira,
+            store_jira_credentials,
+            unlock_jira_credentials,
+            rotate_jira_token,
+            clear_jira_credentials,
+            jira_credentials_status,
             test_jira_connection,
             fetch_jira_tickets,
+            sync_worklogs,
+            draft_entries_from_jira,
+            list_jira_transitions,
+            transition_jira_issue,
+            update_jira_from_entry,
             get_available_tickets_for_entry,
+            get_jira_tickets,
+            detect_jira_keys,
             get_all_projects,
             create_project,
             update_project,
+            rename_project,
+            merge_projects,
+            get_project_aliases,
             delete_project,
             toggle_project_status,
             get_all_tags,
             create_tag,
             update_tag,
+            rename_tag,
+            merge_tags,
+            get_tag_aliases,
             delete_tag,
             toggle_tag_status,
             get_all_activity_types,
@@ -1415,32 +1893,54 @@ pub fn run() {
             toggle_activity_type_status,
             get_preference,
             set_preference,
+            export_diagnostics,
             generate_activity_report,
             export_activity_report_to_docx
         ])
         .setup(|app| {
+            // Logs dans le dossier de données, filtrés selon les préférences
+            let log_filter = app
+                .store("store.json")
+                .ok()
+                .and_then(|store| store.get(LOG_FILTER_PREFERENCE))
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_else(|| DEFAULT_LOG_FILTER.to_string());
+            let log_dir = app.path().app_data_dir()?.join("logs");
+            if let Some(locale) = app
+                .store("store.json")
+                .ok()
+                .and_then(|store| store.get(LOCALE_PREFERENCE))
+                .and_then(|value| value.as_str().map(str::to_string))
+            {
+                set_locale(&locale);
+            }
+            if let Err(e) = logging::init(&log_dir, &log_filter) {
+                eprintln!("Logging not initialized: {}", e);
+            }
+
             // Charger le fichier .env s'il existe
-            if let Err(_) = dotenv::dotenv() {
-                println!("No .env file found, using system environment variables");
+            if dotenv::dotenv().is_err() {
+                tracing::info!("no .env file found, using system environment variables");
             }
             
-            // Initialiser Jira avec les variables d'environnement au démarrage
-            let base_url = std::env::var("JIRA_BASE_URL").unwrap_or_default();
-            let email = std::env::var("JIRA_EMAIL").unwrap_or_default();
-            let api_token = std::env::var("JIRA_API_TOKEN").unwrap_or_default();
-            
-            println!("Setup: Loading Jira config - base_url='{}', email='{}', token='{}'", 
-                base_url, 
-                email, 
-                if api_token.is_empty() { "empty" } else { "***set***" }
-            );
-            
-            if !base_url.is_empty() && !email.is_empty() && !api_token.is_empty() {
-                let state = app.state::<AppState>();
-                let client = JiraClient::new(base_url, email, api_token);
-                let mut jira_client = state.jira_client.lock().unwrap();
-                *jira_client = Some(client);
-            }
+            // Connecter Jira sans retarder l'ouverture de la fenêtre
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = connect_jira_at_startup(&handle).await {
+                    tracing::warn!("Jira not connected at startup: {}", e);
+                }
+            });
+
+            // Reconstruire l'index du journal en tâche de fond pour que la
+            // première recherche ou le premier rapport soient rapides
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                let state = handle.state::<AppState>();
+                if let Err(e) = with_journal_index(&handle, &state, |_| Ok(())) {
+                    tracing::warn!("journal index not rebuilt: {}", e);
+                }
+            });
+            spawn_ticket_refresh(app.handle().clone());
             
             Ok(())
         })
@@ -0,0 +1,896 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::duration::EntryDuration;
+
+// Format Markdown des fichiers journal.
+//
+// Un fichier est une suite de blocs : un préambule libre, puis des entrées
+// introduites par un titre `## <timestamp>`. Chaque entrée contient un
+// commentaire masqué portant son identifiant, un commentaire masqué par
+// worklog Jira synchronisé, du texte libre éventuel et des champs
+// `**Libellé**: valeur`. Une valeur peut s'étendre sur plusieurs lignes :
+// les lignes de continuation qui ressembleraient à de la structure (titre,
+// champ, séparateur `---`, identifiant) sont préfixées par `\`, ce qui est
+// aussi l'échappement Markdown standard. Tout ce que le parseur ne reconnaît pas
+// est conservé (`notes`, `extra_fields`, `preamble`) pour être réécrit tel quel.
+
+pub const ENTRY_SEPARATOR: &str = "\n---\n\n";
+
+const ENTRY_HEADING_PREFIX: &str = "## ";
+const ENTRY_ID_PREFIX: &str = "<!-- id:";
+const ENTRY_ID_SUFFIX: &str = "-->";
+const WORKLOG_PREFIX: &str = "<!-- worklog:";
+// Suffixe "saut de ligne forcé" ajouté à la dernière ligne de chaque champ
+const HARD_BREAK: &str = "  ";
+
+const LABEL_PROJECT: &str = "Projet";
+const LABEL_TIME_RANGE: &str = "Plage horaire";
+const LABEL_ENTRY_TYPE: &str = "Type d'activité";
+const LABEL_DESCRIPTION: &str = "Description";
+const LABEL_DURATION: &str = "Durée";
+const LABEL_RESULTS: &str = "Résultats";
+const LABEL_BLOCKERS: &str = "Blocages";
+const LABEL_LINKS: &str = "Liens";
+const LABEL_TAGS: &str = "Tags";
+const LABEL_REFLECTIONS: &str = "Réflexions";
+
+const DEFAULT_ENTRY_TYPE: &str = "développement";
+const NO_TAGS: &str = "Aucun";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JiraTicketRef {
+    pub key: String,
+    pub summary: Option<String>,
+    // Adresse du lien tel qu'écrit dans le journal ; sans elle, le lien est
+    // construit à partir de l'instance passée à `generate_markdown_entry`
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+// Worklog Jira créé à partir de l'entrée : une nouvelle synchronisation le met à
+// jour au lieu d'en créer un autre
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorklogRef {
+    pub issue_key: String,
+    pub worklog_id: String,
+}
+
+// Champ `**Libellé**: valeur` que le parseur ne connaît pas
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExtraField {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ParsedJournalEntry {
+    // Identifiant persistant, écrit dans un commentaire HTML masqué sous le titre
+    #[serde(default)]
+    pub id: String,
+    pub timestamp: String,
+    pub project: String,
+    pub description: String,
+    pub duration: EntryDuration,
+    pub tags: Vec<String>,
+    pub time_range: String,
+    pub entry_type: String,
+    pub results: String,
+    pub blockers: String,
+    pub links: Vec<Link>,
+    pub reflections: String,
+    pub jira_tickets: Vec<JiraTicketRef>,
+    #[serde(default)]
+    pub worklogs: Vec<WorklogRef>,
+    // Texte libre de l'entrée qui n'appartient à aucun champ
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub extra_fields: Vec<ExtraField>,
+}
+
+impl ParsedJournalEntry {
+    fn empty(timestamp: String) -> Self {
+        Self {
+            id: String::new(),
+            timestamp,
+            project: String::new(),
+            description: String::new(),
+            duration: EntryDuration::default(),
+            tags: Vec::new(),
+            time_range: String::new(),
+            entry_type: DEFAULT_ENTRY_TYPE.to_string(),
+            results: String::new(),
+            blockers: String::new(),
+            links: Vec::new(),
+            reflections: String::new(),
+            jira_tickets: Vec::new(),
+            worklogs: Vec::new(),
+            notes: String::new(),
+            extra_fields: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalDocument {
+    // Texte situé avant la première entrée
+    pub preamble: String,
+    pub entries: Vec<ParsedJournalEntry>,
+}
+
+fn jira_link_regex() -> &'static regex::Regex {
+    static REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        // [KEY - SUMMARY](URL) ou [KEY](URL), avec une URL en .../browse/KEY
+        regex::Regex::new(r"\[([A-Z][A-Z0-9_]*-\d+)(?:\s*-\s*([^\]]+))?\]\(([^)]*browse/[^)]+)\)")
+            .unwrap()
+    })
+}
+
+// Lien Jira découpé pour en changer l'adresse : texte, adresse de l'instance
+// (vide pour un lien relatif), clé
+fn jira_link_parts_regex() -> &'static regex::Regex {
+    static REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        regex::Regex::new(
+            r"(\[[A-Z][A-Z0-9_]*-\d+(?:\s*-\s*[^\]]+)?\]\()([^)]*?)/?browse/([^)]+)\)",
+        )
+        .unwrap()
+    })
+}
+
+fn browse_url(base_url: Option<&str>, key: &str) -> String {
+    match base_url
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+    {
+        Some(base_url) => format!("{}/browse/{}", base_url, key),
+        None => format!("browse/{}", key),
+    }
+}
+
+// Réécrit les liens Jira du texte vers `base_url` (liens relatifs si None).
+// Avec `previous_base_url`, seuls les liens de cette instance et les liens
+// relatifs sont concernés. Renvoie le texte et le nombre de liens modifiés.
+pub fn rewrite_jira_links(
+    content: &str,
+    base_url: Option<&str>,
+    previous_base_url: Option<&str>,
+) -> (String, usize) {
+    let normalize = |url: &str| url.trim().trim_end_matches('/').to_string();
+    let base_url = base_url.map(normalize).filter(|url| !url.is_empty());
+    let previous_base_url = previous_base_url.map(normalize);
+    let mut rewritten = 0;
+
+    let content = jira_link_parts_regex().replace_all(content, |cap: &regex::Captures| {
+        let current = normalize(&cap[2]);
+        let concerned = match &previous_base_url {
+            Some(previous) => current.is_empty() || &current == previous,
+            None => true,
+        };
+        if !concerned || Some(&current) == base_url.as_ref() {
+            return cap[0].to_string();
+        }
+        rewritten += 1;
+        format!("{}{})", &cap[1], browse_url(base_url.as_deref(), &cap[3]))
+    });
+    (content.into_owned(), rewritten)
+}
+
+fn markdown_link_regex() -> &'static regex::Regex {
+    static REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    REGEX.get_or_init(|| regex::Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap())
+}
+
+fn field_label_regex() -> &'static regex::Regex {
+    static REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    REGEX.get_or_init(|| regex::Regex::new(r"^\*\*([^*]+)\*\*:").unwrap())
+}
+
+fn parse_entry_id(line: &str) -> Option<String> {
+    let id = line
+        .trim()
+        .strip_prefix(ENTRY_ID_PREFIX)?
+        .strip_suffix(ENTRY_ID_SUFFIX)?
+        .trim();
+
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+// `<!-- worklog: PROJ-12 10042 -->`
+fn parse_worklog(line: &str) -> Option<WorklogRef> {
+    let content = line
+        .trim()
+        .strip_prefix(WORKLOG_PREFIX)?
+        .strip_suffix(ENTRY_ID_SUFFIX)?;
+    let mut parts = content.split_whitespace();
+    let (issue_key, worklog_id) = (parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(WorklogRef {
+        issue_key: issue_key.to_string(),
+        worklog_id: worklog_id.to_string(),
+    })
+}
+
+// Renvoie le libellé et le reste de la ligne pour une ligne `**Libellé**: valeur`
+fn parse_field_label(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    let captures = field_label_regex().captures(line)?;
+    let label = captures.get(1)?.as_str();
+    let rest = &line[captures.get(0)?.end()..];
+    Some((label, rest))
+}
+
+fn is_entry_heading(line: &str) -> bool {
+    line.starts_with(ENTRY_HEADING_PREFIX)
+}
+
+fn is_separator(line: &str) -> bool {
+    line.trim() == "---"
+}
+
+// Une ligne de texte qui serait interprétée comme de la structure
+fn is_structural(line: &str) -> bool {
+    is_entry_heading(line)
+        || is_separator(line)
+        || parse_entry_id(line).is_some()
+        || parse_worklog(line).is_some()
+        || parse_field_label(line).is_some()
+}
+
+fn escape_line(line: &str) -> String {
+    if line.starts_with('\\') || is_structural(line) {
+        format!("\\{}", line)
+    } else {
+        line.to_string()
+    }
+}
+
+fn unescape_line(line: &str) -> &str {
+    match line.strip_prefix('\\') {
+        Some(rest) if rest.starts_with('\\') || is_structural(rest) => rest,
+        _ => line,
+    }
+}
+
+// Identifiant déterministe pour les entrées écrites avant l'ajout des IDs :
+// il reste stable tant que le texte de l'entrée ne change pas. Les copies
+// identiques d'une même entrée sont distinguées par leur rang parmi ces
+// copies ; la première garde l'identifiant calculé sur le seul texte.
+fn legacy_entry_id(section: &str, occurrence: usize) -> String {
+    let content = section.trim().trim_end_matches("---").trim_end();
+    let name = match occurrence {
+        0 => content.to_string(),
+        n => format!("{}\n#{}", content, n),
+    };
+    uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, name.as_bytes()).to_string()
+}
+
+// Retire les lignes vides qui ne servent qu'à la mise en page autour d'un bloc
+fn trim_blank_lines(mut lines: Vec<String>) -> Vec<String> {
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let leading = lines.iter().take_while(|line| line.is_empty()).count();
+    lines.drain(..leading);
+    lines
+}
+
+fn field_value(lines: Vec<String>) -> String {
+    let mut lines = lines;
+    while lines.len() > 1 && lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let value = lines.join("\n");
+    match value.strip_suffix(HARD_BREAK) {
+        Some(stripped) => stripped.to_string(),
+        None => value,
+    }
+}
+
+fn parse_tags(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value == NO_TAGS || value.is_empty() {
+        return Vec::new();
+    }
+
+    value
+        .split_whitespace()
+        .map(|tag| tag.replace('#', ""))
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+fn parse_links(value: &str, entry: &mut ParsedJournalEntry) {
+    let value = value.trim();
+    if value.is_empty() || value == NO_TAGS {
+        return;
+    }
+
+    // Parse d'abord les tickets Jira, puis les autres liens sur le texte restant
+    let mut non_jira_links = value.to_string();
+    for cap in jira_link_regex().captures_iter(value) {
+        if let (Some(full_match), Some(key_match)) = (cap.get(0), cap.get(1)) {
+            entry.jira_tickets.push(JiraTicketRef {
+                key: key_match.as_str().to_string(),
+                summary: cap.get(2).map(|m| m.as_str().to_string()),
+                url: cap.get(3).map(|m| m.as_str().to_string()),
+            });
+            non_jira_links = non_jira_links.replacen(full_match.as_str(), "", 1);
+        }
+    }
+
+    for cap in markdown_link_regex().captures_iter(&non_jira_links) {
+        if let (Some(text), Some(url)) = (cap.get(1), cap.get(2)) {
+            entry.links.push(Link {
+                text: text.as_str().to_string(),
+                url: url.as_str().to_string(),
+            });
+        }
+    }
+}
+
+struct EntryBuilder {
+    entry: ParsedJournalEntry,
+    raw_lines: Vec<String>,
+    notes: Vec<String>,
+    seen_labels: Vec<String>,
+    current_field: Option<(String, Vec<String>)>,
+}
+
+impl EntryBuilder {
+    fn new(heading: &str) -> Self {
+        Self {
+            entry: ParsedJournalEntry::empty(heading.trim().to_string()),
+            raw_lines: vec![heading.to_string()],
+            notes: Vec::new(),
+            seen_labels: Vec::new(),
+            current_field: None,
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        self.raw_lines.push(line.to_string());
+
+        if is_separator(line) {
+            self.close_field();
+        } else if let Some(id) = parse_entry_id(line) {
+            self.close_field();
+            self.entry.id = id;
+        } else if let Some(worklog) = parse_worklog(line) {
+            self.close_field();
+            self.entry.worklogs.push(worklog);
+        } else if let Some((label, rest)) = parse_field_label(line) {
+            self.close_field();
+            let first_line = rest.strip_prefix(' ').unwrap_or(rest);
+            self.current_field = Some((label.to_string(), vec![first_line.to_string()]));
+        } else {
+            let content = unescape_line(line).to_string();
+            match &mut self.current_field {
+                Some((_, lines)) => lines.push(content),
+                None => self.notes.push(content),
+            }
+        }
+    }
+
+    fn close_field(&mut self) {
+        let Some((label, lines)) = self.current_field.take() else {
+            return;
+        };
+        let value = field_value(lines);
+
+        // Un libellé connu rencontré deux fois est conservé comme champ inconnu
+        let is_duplicate = self.seen_labels.contains(&label);
+        self.seen_labels.push(label.clone());
+        let entry = &mut self.entry;
+
+        match label.as_str() {
+            LABEL_PROJECT if !is_duplicate => entry.project = value.trim().to_string(),
+            LABEL_TIME_RANGE if !is_duplicate => entry.time_range = value.trim().to_string(),
+            LABEL_ENTRY_TYPE if !is_duplicate => entry.entry_type = value.trim().to_string(),
+            LABEL_DESCRIPTION if !is_duplicate => entry.description = value,
+            LABEL_DURATION if !is_duplicate => entry.duration = EntryDuration::from_text(&value),
+            LABEL_RESULTS if !is_duplicate => entry.results = value,
+            LABEL_BLOCKERS if !is_duplicate => entry.blockers = value,
+            LABEL_LINKS if !is_duplicate => parse_links(&value, entry),
+            LABEL_TAGS if !is_duplicate => entry.tags = parse_tags(&value),
+            LABEL_REFLECTIONS if !is_duplicate => entry.reflections = value,
+            _ => entry.extra_fields.push(ExtraField { label, value }),
+        }
+    }
+
+    fn finish(mut self, legacy_sections: &mut HashMap<String, usize>) -> ParsedJournalEntry {
+        self.close_field();
+        self.entry.notes = trim_blank_lines(self.notes).join("\n");
+
+        if self.entry.id.is_empty() {
+            let section = self.raw_lines.join("\n");
+            let occurrence = legacy_sections
+                .entry(legacy_entry_id(&section, 0))
+                .or_insert(0);
+            self.entry.id = legacy_entry_id(&section, *occurrence);
+            *occurrence += 1;
+        }
+
+        self.entry
+    }
+}
+
+pub fn parse_journal_document(content: &str) -> JournalDocument {
+    let mut preamble = Vec::new();
+    let mut entries = Vec::new();
+    let mut current: Option<EntryBuilder> = None;
+    let mut legacy_sections = HashMap::new();
+
+    for line in content.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        if is_entry_heading(line) {
+            if let Some(builder) = current.take() {
+                entries.push(builder.finish(&mut legacy_sections));
+            }
+            current = Some(EntryBuilder::new(&line[ENTRY_HEADING_PREFIX.len()..]));
+            continue;
+        }
+
+        match &mut current {
+            Some(builder) => builder.push_line(line),
+            None => preamble.push(line.to_string()),
+        }
+    }
+
+    if let Some(builder) = current {
+        entries.push(builder.finish(&mut legacy_sections));
+    }
+
+    JournalDocument {
+        preamble: trim_blank_lines(preamble).join("\n"),
+        entries,
+    }
+}
+
+// Parse les entrées d'un fichier journal
+pub fn parse_journal_entries(content: &str) -> Vec<ParsedJournalEntry> {
+    parse_journal_document(content).entries
+}
+
+fn push_block(content: &mut String, text: &str) {
+    for line in text.split('\n') {
+        content.push_str(&escape_line(line));
+        content.push('\n');
+    }
+}
+
+fn push_field(content: &mut String, label: &str, value: &str) {
+    let mut lines = value.split('\n');
+    let first = lines.next().unwrap_or_default();
+    let rest: Vec<&str> = lines.collect();
+
+    content.push_str(&format!("**{}**: {}", label, first));
+    if rest.is_empty() {
+        content.push_str(HARD_BREAK);
+    }
+    content.push('\n');
+
+    for (index, line) in rest.iter().enumerate() {
+        let line = if index + 1 == rest.len() {
+            format!("{}{}", line, HARD_BREAK)
+        } else {
+            line.to_string()
+        };
+        content.push_str(&escape_line(&line));
+        content.push('\n');
+    }
+}
+
+// Vrai si une durée n'est pas écrite sous sa forme canonique, par exemple
+// "45 minutes" dans l'ancien format
+pub fn has_non_canonical_durations(content: &str) -> bool {
+    let prefix = format!("**{}**:", LABEL_DURATION);
+    content
+        .lines()
+        .filter_map(|line| line.strip_prefix(prefix.as_str()))
+        .any(|value| {
+            let value = value.trim();
+            EntryDuration::from_text(value).to_string() != value
+        })
+}
+
+// Les tickets sans adresse (ajoutés depuis le formulaire, Jira...) sont liés
+// à `jira_base_url` ; ceux relus dans le journal gardent leur lien, que seul
+// `rewrite_jira_links` fait changer d'instance.
+pub fn generate_markdown_entry(entry: &ParsedJournalEntry, jira_base_url: Option<&str>) -> String {
+    let timestamp = entry.timestamp.replace('\n', " ");
+    let mut content = format!("{}{}\n", ENTRY_HEADING_PREFIX, timestamp);
+    if !entry.id.is_empty() {
+        content.push_str(&format!(
+            "{} {} {}\n",
+            ENTRY_ID_PREFIX, entry.id, ENTRY_ID_SUFFIX
+        ));
+    }
+    for worklog in &entry.worklogs {
+        content.push_str(&format!(
+            "{} {} {} {}\n",
+            WORKLOG_PREFIX, worklog.issue_key, worklog.worklog_id, ENTRY_ID_SUFFIX
+        ));
+    }
+
+    if !entry.notes.is_empty() {
+        push_block(&mut content, &entry.notes);
+    }
+
+    push_field(&mut content, LABEL_PROJECT, &entry.project);
+
+    if !entry.time_range.is_empty() {
+        push_field(&mut content, LABEL_TIME_RANGE, &entry.time_range);
+    }
+
+    push_field(&mut content, LABEL_ENTRY_TYPE, &entry.entry_type);
+    push_field(&mut content, LABEL_DESCRIPTION, &entry.description);
+    push_field(&mut content, LABEL_DURATION, &entry.duration.to_string());
+
+    if !entry.results.is_empty() {
+        push_field(&mut content, LABEL_RESULTS, &entry.results);
+    }
+
+    if !entry.blockers.is_empty() {
+        push_field(&mut content, LABEL_BLOCKERS, &entry.blockers);
+    }
+
+    // Générer les liens Jira
+    let mut jira_markdown_links = Vec::new();
+    for ticket in &entry.jira_tickets {
+        let display_text = if let Some(summary) = &ticket.summary {
+            format!("{} - {}", ticket.key, summary)
+        } else {
+            ticket.key.clone()
+        };
+        let url = match &ticket.url {
+            Some(url) => url.clone(),
+            None => browse_url(jira_base_url, &ticket.key),
+        };
+        jira_markdown_links.push(format!("[{}]({})", display_text, url));
+    }
+
+    // Générer les liens normaux
+    let regular_markdown_links = entry
+        .links
+        .iter()
+        .filter(|link| !link.text.is_empty() && !link.url.is_empty())
+        .map(|link| format!("[{}]({})", link.text, link.url));
+
+    // Combiner tous les liens pour la section "Liens"
+    let mut all_markdown_links = jira_markdown_links;
+    all_markdown_links.extend(regular_markdown_links);
+
+    if !all_markdown_links.is_empty() {
+        push_field(&mut content, LABEL_LINKS, &all_markdown_links.join(", "));
+    }
+
+    let tags_str = if entry.tags.is_empty() {
+        NO_TAGS.to_string()
+    } else {
+        entry
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    push_field(&mut content, LABEL_TAGS, &tags_str);
+
+    if !entry.reflections.is_empty() {
+        push_field(&mut content, LABEL_REFLECTIONS, &entry.reflections);
+    }
+
+    for field in &entry.extra_fields {
+        push_field(&mut content, &field.label, &field.value);
+    }
+
+    content
+}
+
+pub fn render_journal_document(document: &JournalDocument, jira_base_url: Option<&str>) -> String {
+    let entries = document
+        .entries
+        .iter()
+        .map(|entry| generate_markdown_entry(entry, jira_base_url))
+        .collect::<Vec<_>>()
+        .join(ENTRY_SEPARATOR);
+
+    if document.preamble.is_empty() {
+        entries
+    } else if entries.is_empty() {
+        format!("{}\n", document.preamble)
+    } else {
+        format!("{}\n\n{}", document.preamble, entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        browse_url, generate_markdown_entry, parse_journal_document, parse_journal_entries,
+        render_journal_document, rewrite_jira_links, ExtraField, JiraTicketRef, Link,
+        ParsedJournalEntry, WorklogRef,
+    };
+    use crate::duration::{Duration, EntryDuration};
+    use proptest::prelude::*;
+
+    fn sample_entry() -> ParsedJournalEntry {
+        ParsedJournalEntry {
+            id: "abc-123".to_string(),
+            timestamp: "06/04/2026 09:00".to_string(),
+            project: "Mandate".to_string(),
+            description: "Export".to_string(),
+            duration: EntryDuration::Known(Duration::from_minutes(30)),
+            tags: vec!["bug".to_string()],
+            time_range: "09:00-09:30".to_string(),
+            entry_type: "développement".to_string(),
+            results: String::new(),
+            blockers: String::new(),
+            links: Vec::new(),
+            reflections: String::new(),
+            jira_tickets: Vec::new(),
+            worklogs: Vec::new(),
+            notes: String::new(),
+            extra_fields: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_legacy_entries() {
+        let content = "## 06/04/2026 09:00\n**Projet**: Mandate  \n**Type d'activité**: debug  \n**Description**: Première ligne\n\nSeconde ligne  \n**Durée**: 45 minutes  \n**Liens**: [CSE-12 - Export](https://x.atlassian.net/browse/CSE-12), [Doc](https://doc)  \n**Tags**: #bug #export  \n\n\n---\n\n## 06/04/2026 10:00\n**Projet**: Claims  \n**Tags**: Aucun  \n";
+
+        let entries = parse_journal_entries(content);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].project, "Mandate");
+        assert_eq!(entries[0].entry_type, "debug");
+        assert_eq!(entries[0].description, "Première ligne\n\nSeconde ligne");
+        assert_eq!(
+            entries[0].duration.known(),
+            Some(Duration::from_minutes(45))
+        );
+        assert_eq!(entries[0].tags, vec!["bug", "export"]);
+        assert_eq!(
+            entries[0].jira_tickets,
+            vec![JiraTicketRef {
+                key: "CSE-12".to_string(),
+                summary: Some("Export".to_string()),
+                url: Some("https://x.atlassian.net/browse/CSE-12".to_string()),
+            }]
+        );
+        assert_eq!(
+            entries[0].links,
+            vec![Link {
+                text: "Doc".to_string(),
+                url: "https://doc".to_string(),
+            }]
+        );
+        assert_eq!(entries[1].project, "Claims");
+        assert!(entries[1].tags.is_empty());
+        assert_eq!(entries[1].entry_type, "développement");
+    }
+
+    #[test]
+    fn rewrites_jira_links_to_the_configured_instance() {
+        let content = "**Liens**: [CSE-12 - Export](https://votre-instance.atlassian.net/browse/CSE-12), [OPS-3](browse/OPS-3), [Doc](https://doc/browse/x.md)  \n**Description**: voir [CSE-13](https://autre.acme.com/browse/CSE-13)  \n";
+
+        let (all, count) = rewrite_jira_links(content, Some("https://acme.atlassian.net/"), None);
+        assert_eq!(count, 3);
+        assert_eq!(
+            all,
+            "**Liens**: [CSE-12 - Export](https://acme.atlassian.net/browse/CSE-12), [OPS-3](https://acme.atlassian.net/browse/OPS-3), [Doc](https://doc/browse/x.md)  \n**Description**: voir [CSE-13](https://acme.atlassian.net/browse/CSE-13)  \n"
+        );
+
+        // Seuls les liens de l'ancienne instance, et les liens relatifs
+        let (only_previous, count) = rewrite_jira_links(
+            content,
+            Some("https://acme.atlassian.net"),
+            Some("https://votre-instance.atlassian.net"),
+        );
+        assert_eq!(count, 2);
+        assert!(only_previous.contains("(https://autre.acme.com/browse/CSE-13)"));
+
+        // Un lien relatif reste reconnu comme ticket Jira
+        assert_eq!(browse_url(None, "OPS-3"), "browse/OPS-3");
+        assert_eq!(
+            parse_journal_entries(&format!("## 06/04/2026 09:00\n{}", content))[0].jira_tickets[1]
+                .key,
+            "OPS-3"
+        );
+        assert_eq!(
+            rewrite_jira_links(&all, Some("https://acme.atlassian.net"), None).1,
+            0
+        );
+    }
+
+    #[test]
+    fn keeps_the_stored_link_of_each_ticket() {
+        let mut entry = sample_entry();
+        entry.jira_tickets = vec![JiraTicketRef {
+            key: "CSE-12".to_string(),
+            summary: None,
+            url: None,
+        }];
+
+        // Un nouveau ticket est lié à l'instance donnée
+        let markdown = generate_markdown_entry(&entry, Some("https://acme.atlassian.net"));
+        assert!(markdown.contains("[CSE-12](https://acme.atlassian.net/browse/CSE-12)"));
+
+        // Relu puis réécrit sans instance configurée, il garde son adresse
+        let document = parse_journal_document(&markdown);
+        let rendered = render_journal_document(&document, None);
+        assert!(rendered.contains("[CSE-12](https://acme.atlassian.net/browse/CSE-12)"));
+        assert!(generate_markdown_entry(&entry, None).contains("[CSE-12](browse/CSE-12)"));
+    }
+
+    #[test]
+    fn keeps_structure_like_lines_inside_fields() {
+        let mut entry = sample_entry();
+        entry.description =
+            "## Pas un titre\n**Gras**: pas un champ\n---\n<!-- id: faux -->\n<!-- worklog: CSE-1 7 -->"
+                .to_string();
+        entry.worklogs = vec![WorklogRef {
+            issue_key: "CSE-12".to_string(),
+            worklog_id: "10042".to_string(),
+        }];
+
+        let markdown = generate_markdown_entry(&entry, None);
+        let entries = parse_journal_entries(&markdown);
+
+        assert_eq!(entries, vec![entry]);
+    }
+
+    #[test]
+    fn preserves_unknown_fields_notes_and_preamble() {
+        let content = "# Journal\n\n## 06/04/2026 09:00\nNote libre\n**Projet**: Mandate  \n**Humeur**: bonne  \n**Tags**: Aucun  \n";
+
+        let document = parse_journal_document(content);
+        let entry = &document.entries[0];
+
+        assert_eq!(document.preamble, "# Journal");
+        assert_eq!(entry.notes, "Note libre");
+        assert_eq!(
+            entry.extra_fields,
+            vec![ExtraField {
+                label: "Humeur".to_string(),
+                value: "bonne".to_string(),
+            }]
+        );
+        assert_eq!(
+            parse_journal_document(&render_journal_document(&document, None)),
+            document
+        );
+    }
+
+    fn free_text() -> impl Strategy<Value = String> {
+        prop::collection::vec("[^\r\n]*", 0..5).prop_map(|lines| lines.join("\n"))
+    }
+
+    fn single_line() -> impl Strategy<Value = String> {
+        "[^\r\n]*".prop_map(|value| value.trim().to_string())
+    }
+
+    fn entry_duration() -> impl Strategy<Value = EntryDuration> {
+        prop_oneof![
+            (0u32..100_000)
+                .prop_map(|minutes| EntryDuration::Known(Duration::from_minutes(minutes))),
+            single_line().prop_map(|text| EntryDuration::from_text(&text)),
+        ]
+    }
+
+    // Texte libre hors champ : les lignes vides en bordure relèvent de la mise en page
+    fn notes() -> impl Strategy<Value = String> {
+        prop::collection::vec("[^\r\n]*", 0..4).prop_map(|lines| {
+            let text = lines.join("\n");
+            text.trim_matches('\n').to_string()
+        })
+    }
+
+    fn link() -> impl Strategy<Value = Link> {
+        (
+            "[A-Za-z0-9 ,.]{0,10}[A-Za-z0-9]",
+            "https://[a-z]{1,8}\\.[a-z]{2,3}/[a-z0-9]{0,8}",
+        )
+            .prop_map(|(text, url)| Link { text, url })
+    }
+
+    fn jira_ticket() -> impl Strategy<Value = JiraTicketRef> {
+        (
+            "[A-Z][A-Z0-9]{1,5}-[1-9][0-9]{0,4}",
+            prop::option::of("[A-Za-z0-9][A-Za-z0-9 ,.:()-]{0,20}[A-Za-z0-9]"),
+        )
+            .prop_map(|(key, summary)| JiraTicketRef {
+                url: Some(format!("https://x.atlassian.net/browse/{}", key)),
+                key,
+                summary,
+            })
+    }
+
+    fn worklog() -> impl Strategy<Value = WorklogRef> {
+        ("[A-Z][A-Z0-9]{1,5}-[1-9][0-9]{0,4}", "[1-9][0-9]{0,6}").prop_map(
+            |(issue_key, worklog_id)| WorklogRef {
+                issue_key,
+                worklog_id,
+            },
+        )
+    }
+
+    fn extra_field() -> impl Strategy<Value = ExtraField> {
+        ("Champ [A-Za-z]{1,8}", free_text()).prop_map(|(label, value)| ExtraField { label, value })
+    }
+
+    prop_compose! {
+        fn arbitrary_entry()(
+            id in "[a-f0-9]{8}(-[a-f0-9]{4}){3}-[a-f0-9]{12}",
+            timestamp in "[^\r\n]*[^\r\n\\s]",
+            project in single_line(),
+            description in free_text(),
+            duration in entry_duration(),
+            tags in prop::collection::vec("[a-z0-9_-]{1,12}", 0..4),
+            time_range in single_line(),
+            entry_type in "[^\r\n]*[^\r\n\\s]",
+            results in free_text(),
+            blockers in free_text(),
+            links in prop::collection::vec(link(), 0..3),
+            reflections in free_text(),
+            jira_tickets in prop::collection::vec(jira_ticket(), 0..3),
+            worklogs in prop::collection::vec(worklog(), 0..3),
+            notes in notes(),
+            extra_fields in prop::collection::vec(extra_field(), 0..3),
+        ) -> ParsedJournalEntry {
+            ParsedJournalEntry {
+                id,
+                timestamp: timestamp.trim().to_string(),
+                project,
+                description,
+                duration,
+                tags,
+                time_range,
+                entry_type: entry_type.trim().to_string(),
+                results,
+                blockers,
+                links,
+                reflections,
+                jira_tickets,
+                worklogs,
+                notes,
+                extra_fields,
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn entry_round_trips_through_markdown(entry in arbitrary_entry()) {
+            let markdown = generate_markdown_entry(&entry, None);
+            prop_assert_eq!(parse_journal_entries(&markdown), vec![entry]);
+        }
+
+        #[test]
+        fn document_round_trips_through_markdown(
+            entries in prop::collection::vec(arbitrary_entry(), 0..4),
+        ) {
+            let document = super::JournalDocument { preamble: String::new(), entries };
+            let markdown = render_journal_document(&document, None);
+            prop_assert_eq!(parse_journal_document(&markdown), document);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri_plugin_store::StoreExt;
+
+use crate::error::AppError;
+use crate::file_manager::default_journal_dir;
+use crate::jira::{JiraAuthMethod, JiraDeployment, JiraFieldConfig, JiraHttpConfig};
+
+// Les profils sont stockés dans le store des préférences
+const PREFERENCES_STORE: &str = "store.json";
+const PROFILES_KEY: &str = "profiles";
+const ACTIVE_PROFILE_KEY: &str = "active_profile";
+
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JiraProfileConfig {
+    pub base_url: String,
+    // Email (Cloud) ou identifiant (Server), inutile pour un jeton Bearer
+    pub email: String,
+    // Cloud ou Server / Data Center ; détecté à la connexion s'il est absent
+    #[serde(default)]
+    pub deployment: Option<JiraDeployment>,
+    #[serde(default)]
+    pub auth_method: JiraAuthMethod,
+    // Site Jira Cloud visé par un jeton OAuth 2.0
+    #[serde(default)]
+    pub cloud_id: Option<String>,
+    // Champs demandés lors des recherches de tickets
+    #[serde(default)]
+    pub fields: JiraFieldConfig,
+    // Délais, nouvelles tentatives, proxy et certificat des appels Jira
+    #[serde(default)]
+    pub http: JiraHttpConfig,
+    // Projet du journal associé à chaque clé de projet Jira, pour les entrées
+    // proposées à partir de l'activité Jira
+    #[serde(default)]
+    pub project_map: BTreeMap<String, String>,
+    // Clés de projet (ou expressions) repérées dans le texte des entrées, en
+    // plus des clés de `project_map`
+    #[serde(default)]
+    pub key_patterns: Vec<String>,
+}
+
+// Un journal indépendant : son dossier racine, sa configuration Jira et ses
+// catalogues de projets et de tags
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub root_dir: String,
+    #[serde(default)]
+    pub jira: Option<JiraProfileConfig>,
+}
+
+impl Profile {
+    pub fn journal_dir(&self) -> PathBuf {
+        PathBuf::from(&self.root_dir)
+    }
+
+    // Nom du store d'un catalogue ("projects", "tags") pour ce profil. Le
+    // profil par défaut garde les fichiers historiques (`projects.json`).
+    pub fn catalog_store(&self, catalog: &str) -> String {
+        if self.id == DEFAULT_PROFILE_ID {
+            format!("{}.json", catalog)
+        } else {
+            format!("{}-{}.json", catalog, self.id)
+        }
+    }
+
+    // Fichier de l'index SQLite des entrées, dans le dossier de données de
+    // l'application
+    pub fn index_file(&self) -> String {
+        format!("journal-index-{}.db", self.id)
+    }
+
+    // Cache des tickets Jira du profil, à côté de l'index
+    pub fn ticket_cache_file(&self) -> String {
+        format!("jira-tickets-{}.db", self.id)
+    }
+}
+
+fn default_profile() -> Result<Profile, AppError> {
+    let root_dir = default_journal_dir()?;
+    Ok(Profile {
+        id: DEFAULT_PROFILE_ID.to_string(),
+        name: "Journal principal".to_string(),
+        root_dir: root_dir.to_string_lossy().to_string(),
+        jira: None,
+    })
+}
+
+pub fn validate_root_dir(root_dir: &str) -> Result<PathBuf, AppError> {
+    let root_dir = root_dir.trim();
+    if root_dir.is_empty() {
+        return Err(AppError::invalid_field(
+            "root_dir",
+            "Le dossier du journal est obligatoire",
+        ));
+    }
+
+    let path = PathBuf::from(root_dir);
+    if !path.is_absolute() {
+        return Err(AppError::invalid_field(
+            "root_dir",
+            "Le dossier du journal doit être un chemin absolu",
+        ));
+    }
+
+    std::fs::create_dir_all(&path).map_err(|e| {
+        AppError::from(e).with_field("root_dir", "Impossible de créer le dossier du journal")
+    })?;
+    Ok(path)
+}
+
+pub fn load_profiles(app: &tauri::AppHandle) -> Result<Vec<Profile>, AppError> {
+    let store = app.store(PREFERENCES_STORE)?;
+    let mut profiles: Vec<Profile> = store
+        .get(PROFILES_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+
+    // Le profil par défaut existe toujours, même avant toute configuration
+    if !profiles
+        .iter()
+        .any(|profile| profile.id == DEFAULT_PROFILE_ID)
+    {
+        profiles.insert(0, default_profile()?);
+    }
+
+    Ok(profiles)
+}
+
+pub fn save_profiles(app: &tauri::AppHandle, profiles: &[Profile]) -> Result<(), AppError> {
+    let store = app.store(PREFERENCES_STORE)?;
+    store.set(PROFILES_KEY, serde_json::to_value(profiles)?);
+    Ok(store.save()?)
+}
+
+pub fn active_profile(app: &tauri::AppHandle) -> Result<Profile, AppError> {
+    let store = app.store(PREFERENCES_STORE)?;
+    let active_id = store
+        .get(ACTIVE_PROFILE_KEY)
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string());
+
+    let mut profiles = load_profiles(app)?;
+    let position = profiles
+        .iter()
+        .position(|profile| profile.id == active_id)
+        .unwrap_or(0);
+    Ok(profiles.swap_remove(position))
+}
+
+pub fn set_active_profile(app: &tauri::AppHandle, id: &str) -> Result<Profile, AppError> {
+    let profile = load_profiles(app)?
+        .into_iter()
+        .find(|profile| profile.id == id)
+        .ok_or_else(|| AppError::not_found("Profil non trouvé"))?;
+    validate_root_dir(&profile.root_dir)?;
+
+    let store = app.store(PREFERENCES_STORE)?;
+    store.set(
+        ACTIVE_PROFILE_KEY,
+        serde_json::Value::String(profile.id.clone()),
+    );
+    store.save()?;
+    Ok(profile)
+}
+
+pub fn active_journal_dir(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(active_profile(app)?.journal_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_root_dir, Profile, DEFAULT_PROFILE_ID};
+
+    fn profile(id: &str) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: "Perso".to_string(),
+            root_dir: "/tmp/journal".to_string(),
+            jira: None,
+        }
+    }
+
+    #[test]
+    fn default_profile_keeps_historical_catalog_stores() {
+        assert_eq!(
+            profile(DEFAULT_PROFILE_ID).catalog_store("projects"),
+            "projects.json"
+        );
+        assert_eq!(profile("perso").catalog_store("tags"), "tags-perso.json");
+    }
+
+    #[test]
+    fn rejects_empty_or_relative_root_dirs() {
+        assert!(validate_root_dir("  ").is_err());
+        assert!(validate_root_dir("journal/perso").is_err());
+    }
+}
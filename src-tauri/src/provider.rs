@@ -0,0 +1,289 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::jira::{JiraClient, JiraFields, JiraIssueType, JiraSearchPage, JiraStatus, JiraTicket};
+
+// Préférences choisissant la source des tickets et, pour `fixture`, le
+// fichier JSON à lire
+pub const TICKET_PROVIDER_PREFERENCE: &str = "ticket_provider";
+pub const TICKET_FIXTURE_PREFERENCE: &str = "ticket_fixture_path";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    #[default]
+    Jira,
+    Demo,
+    Fixture,
+}
+
+impl ProviderKind {
+    // Jira sans préférence ; une valeur inconnue est refusée plutôt que
+    // remplacée par des données de démonstration
+    pub fn from_preference(value: Option<&str>) -> Result<Self, AppError> {
+        match value.map(str::trim).unwrap_or_default() {
+            "" | "jira" => Ok(ProviderKind::Jira),
+            "demo" => Ok(ProviderKind::Demo),
+            "fixture" => Ok(ProviderKind::Fixture),
+            other => Err(AppError::invalid_field(
+                TICKET_PROVIDER_PREFERENCE,
+                format!("Source de tickets inconnue : {}", other),
+            )),
+        }
+    }
+}
+
+// Une page de résultats et la source qui l'a fournie, pour que l'interface
+// distingue les vrais tickets des données d'exemple
+#[derive(Debug, Serialize, Clone)]
+pub struct ProviderPage {
+    #[serde(flatten)]
+    pub page: JiraSearchPage,
+    pub provider: ProviderKind,
+}
+
+// Source de tickets : Jira, ou une liste fixe pour la démonstration et les
+// essais hors ligne
+#[async_trait]
+pub trait IssueProvider: Send + Sync {
+    fn kind(&self) -> ProviderKind;
+
+    async fn search_tickets(
+        &self,
+        query: &str,
+        limit: usize,
+        page_token: Option<String>,
+    ) -> Result<JiraSearchPage>;
+
+    // None si le ticket n'existe pas
+    async fn get_ticket(&self, key: &str) -> Result<Option<JiraTicket>>;
+
+    async fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        page_token: Option<String>,
+    ) -> Result<ProviderPage> {
+        Ok(ProviderPage {
+            page: self.search_tickets(query, limit, page_token).await?,
+            provider: self.kind(),
+        })
+    }
+}
+
+#[async_trait]
+impl IssueProvider for JiraClient {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Jira
+    }
+
+    async fn search_tickets(
+        &self,
+        query: &str,
+        limit: usize,
+        page_token: Option<String>,
+    ) -> Result<JiraSearchPage> {
+        JiraClient::search_tickets(self, query, limit, page_token).await
+    }
+
+    async fn get_ticket(&self, key: &str) -> Result<Option<JiraTicket>> {
+        JiraClient::get_ticket(self, key).await
+    }
+}
+
+// Page d'une liste fixe ; le jeton de page est la position du premier ticket.
+// La requête JQL n'est pas interprétée.
+fn static_page(
+    tickets: &[JiraTicket],
+    limit: usize,
+    page_token: Option<String>,
+) -> Result<JiraSearchPage> {
+    let start: usize = match page_token {
+        Some(token) => token
+            .parse()
+            .map_err(|_| AppError::invalid_field("page_token", "Jeton de page invalide"))?,
+        None => 0,
+    };
+    let end = tickets.len().min(start.saturating_add(limit.max(1)));
+    let has_more = end < tickets.len();
+    Ok(JiraSearchPage {
+        tickets: tickets.get(start..end).unwrap_or_default().to_vec(),
+        next_page_token: has_more.then(|| end.to_string()),
+        has_more,
+    })
+}
+
+fn find_ticket(tickets: &[JiraTicket], key: &str) -> Option<JiraTicket> {
+    tickets
+        .iter()
+        .find(|ticket| ticket.key.eq_ignore_ascii_case(key))
+        .cloned()
+}
+
+fn demo_ticket(key: &str, summary: &str, status: &str, issue_type: &str) -> JiraTicket {
+    JiraTicket {
+        key: key.to_string(),
+        fields: JiraFields {
+            summary: summary.to_string(),
+            status: JiraStatus {
+                name: status.to_string(),
+            },
+            issuetype: JiraIssueType {
+                name: issue_type.to_string(),
+            },
+            ..JiraFields::default()
+        },
+    }
+}
+
+// Tickets de démonstration, choisis explicitement dans les préférences
+pub struct DemoProvider {
+    tickets: Vec<JiraTicket>,
+}
+
+impl Default for DemoProvider {
+    fn default() -> Self {
+        Self {
+            tickets: vec![
+                demo_ticket("DEMO-1", "Ticket de démonstration 1", "En cours", "Tâche"),
+                demo_ticket("DEMO-2", "Ticket de démonstration 2", "À faire", "Bug"),
+            ],
+        }
+    }
+}
+
+#[async_trait]
+impl IssueProvider for DemoProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Demo
+    }
+
+    async fn search_tickets(
+        &self,
+        _query: &str,
+        limit: usize,
+        page_token: Option<String>,
+    ) -> Result<JiraSearchPage> {
+        static_page(&self.tickets, limit, page_token)
+    }
+
+    async fn get_ticket(&self, key: &str) -> Result<Option<JiraTicket>> {
+        Ok(find_ticket(&self.tickets, key))
+    }
+}
+
+// Tickets lus dans un fichier JSON : un tableau de tickets au format renvoyé
+// par `fetch_jira_tickets` (`key` et `fields`)
+pub struct FixtureProvider {
+    tickets: Vec<JiraTicket>,
+}
+
+impl FixtureProvider {
+    pub fn load(path: &Path) -> Result<Self, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            AppError::invalid_field(
+                TICKET_FIXTURE_PREFERENCE,
+                format!("Fichier de tickets illisible ({}) : {}", path.display(), e),
+            )
+        })?;
+        let tickets = serde_json::from_str(&content).map_err(|e| {
+            AppError::invalid_field(
+                TICKET_FIXTURE_PREFERENCE,
+                format!("Fichier de tickets invalide ({}) : {}", path.display(), e),
+            )
+        })?;
+        Ok(Self { tickets })
+    }
+}
+
+#[async_trait]
+impl IssueProvider for FixtureProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Fixture
+    }
+
+    async fn search_tickets(
+        &self,
+        _query: &str,
+        limit: usize,
+        page_token: Option<String>,
+    ) -> Result<JiraSearchPage> {
+        static_page(&self.tickets, limit, page_token)
+    }
+
+    async fn get_ticket(&self, key: &str) -> Result<Option<JiraTicket>> {
+        Ok(find_ticket(&self.tickets, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DemoProvider, FixtureProvider, IssueProvider, ProviderKind};
+    use crate::error::{AppError, ErrorCode};
+    use crate::jira::JiraClient;
+
+    #[tokio::test]
+    async fn reports_which_provider_answered() {
+        let page = DemoProvider::default().search("", 1, None).await.unwrap();
+
+        assert_eq!(page.provider, ProviderKind::Demo);
+        assert_eq!(page.page.tickets[0].key, "DEMO-1");
+        assert_eq!(page.page.next_page_token.as_deref(), Some("1"));
+        assert_eq!(
+            serde_json::to_value(&page).unwrap()["provider"],
+            serde_json::json!("demo")
+        );
+
+        let next = DemoProvider::default()
+            .search("", 50, page.page.next_page_token)
+            .await
+            .unwrap();
+        assert_eq!(next.page.tickets[0].key, "DEMO-2");
+        assert!(!next.page.has_more);
+    }
+
+    #[tokio::test]
+    async fn never_falls_back_to_demo_data() {
+        let client = JiraClient::new(String::new(), String::new(), String::new());
+
+        let error = AppError::from(client.search("", 50, None).await.unwrap_err());
+
+        assert_eq!(error.code, ErrorCode::JiraNotConfigured);
+        assert!(ProviderKind::from_preference(Some("mock")).is_err());
+        assert_eq!(
+            ProviderKind::from_preference(None).unwrap(),
+            ProviderKind::Jira
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_tickets_from_a_fixture_file() {
+        let dir = std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tickets.json");
+        std::fs::write(
+            &path,
+            r#"[{ "key": "OPS-1", "fields": {
+                "summary": "Renouveler le certificat",
+                "status": { "name": "À faire" }, "issuetype": { "name": "Tâche" }
+            } }]"#,
+        )
+        .unwrap();
+
+        let provider = FixtureProvider::load(&path).unwrap();
+        let ticket = provider.get_ticket("ops-1").await.unwrap().unwrap();
+
+        assert_eq!(provider.kind(), ProviderKind::Fixture);
+        assert_eq!(ticket.fields.summary, "Renouveler le certificat");
+        assert!(provider.get_ticket("OPS-2").await.unwrap().is_none());
+
+        std::fs::write(&path, "pas du json").unwrap();
+        let error = FixtureProvider::load(&path).err().unwrap();
+        assert_eq!(error.details[0].field, "ticket_fixture_path");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
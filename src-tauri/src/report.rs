@@ -0,0 +1,433 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::duration::DurationIssue;
+use crate::markdown::ParsedJournalEntry;
+use crate::time_range::effective_duration;
+use crate::timestamp::entry_local_day;
+
+const DEFAULT_PROJECT_COLOR: &str = "#007bff";
+const DEFAULT_TAG_COLOR: &str = "#6c757d";
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ActivityReport {
+    pub period_start: String,
+    pub period_end: String,
+    pub total_entries: usize,
+    pub total_hours: f64,
+    pub projects_summary: Vec<ProjectSummary>,
+    pub tags_summary: Vec<TagSummary>,
+    pub activity_types: HashMap<String, usize>,
+    pub daily_breakdown: HashMap<String, f64>,
+    pub monthly_breakdown: HashMap<String, f64>,
+    pub monthly_details: Vec<MonthlyDetail>,
+    // Entrées dont la durée n'a pas pu être comptée
+    pub duration_issues: Vec<DurationIssue>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ProjectSummary {
+    pub name: String,
+    pub entries: usize,
+    pub hours: f64,
+    pub color: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct TagSummary {
+    pub name: String,
+    pub count: usize,
+    pub color: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MonthlyDetail {
+    pub month: String,
+    pub hours: f64,
+    pub projects: Vec<String>,
+    pub tags: Vec<String>,
+    pub project_hours: HashMap<String, f64>,
+    pub tag_hours: HashMap<String, f64>,
+}
+
+// Nombre d'entrées et minutes cumulées pour un projet, un tag...
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct Tally {
+    pub entries: usize,
+    pub minutes: u64,
+}
+
+impl Tally {
+    fn add(&mut self, other: Tally) {
+        self.entries += other.entries;
+        self.minutes += other.minutes;
+    }
+
+    pub fn hours(&self) -> f64 {
+        self.minutes as f64 / 60.0
+    }
+}
+
+// Agrégat d'une journée, calculé une fois à l'indexation du fichier et
+// recalculé seulement quand celui-ci change. Un rapport n'est plus qu'une
+// somme d'agrégats journaliers.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct DayAggregate {
+    pub date: String,
+    pub total: Tally,
+    pub projects: BTreeMap<String, Tally>,
+    pub tags: BTreeMap<String, Tally>,
+    pub activity_types: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub duration_issues: Vec<DurationIssue>,
+}
+
+impl DayAggregate {
+    // Agrégats d'un fichier, une entrée comptant pour sa journée locale (voir
+    // `entry_local_day`), qui peut différer d'un jour de celle du fichier
+    pub fn for_file(file_date: &str, entries: &[ParsedJournalEntry]) -> Vec<Self> {
+        let mut days: BTreeMap<String, DayAggregate> = BTreeMap::new();
+        for entry in entries {
+            let day = entry_local_day(&entry.timestamp, file_date);
+            days.entry(day.clone())
+                .or_insert_with(|| DayAggregate {
+                    date: day,
+                    ..DayAggregate::default()
+                })
+                .add_entry(file_date, entry);
+        }
+        days.into_values().collect()
+    }
+
+    fn add_entry(&mut self, file_date: &str, entry: &ParsedJournalEntry) {
+        // Sans durée saisie, celle de la plage horaire est comptée ; une durée
+        // illisible n'est pas comptée mais signalée
+        let minutes = match effective_duration(entry) {
+            Ok(duration) => duration.minutes() as u64,
+            Err(error) => {
+                self.duration_issues.push(DurationIssue {
+                    date: file_date.to_string(),
+                    entry_id: entry.id.clone(),
+                    timestamp: entry.timestamp.clone(),
+                    value: entry.duration.to_string(),
+                    reason: error.to_string(),
+                });
+                0
+            }
+        };
+        let tally = Tally {
+            entries: 1,
+            minutes,
+        };
+        self.total.add(tally);
+        self.projects
+            .entry(entry.project.clone())
+            .or_default()
+            .add(tally);
+        for tag in &entry.tags {
+            self.tags.entry(tag.clone()).or_default().add(tally);
+        }
+        *self
+            .activity_types
+            .entry(entry.entry_type.clone())
+            .or_insert(0) += 1;
+    }
+}
+
+// Regroupe sous leur nom actuel les projets et tags renommés ou fusionnés.
+// Les alias ont pour clé l'ancien nom en minuscules (voir `Catalog::aliases`).
+pub fn apply_aliases(
+    days: &mut [DayAggregate],
+    project_aliases: &BTreeMap<String, String>,
+    tag_aliases: &BTreeMap<String, String>,
+) {
+    fn merge(
+        tallies: BTreeMap<String, Tally>,
+        aliases: &BTreeMap<String, String>,
+    ) -> BTreeMap<String, Tally> {
+        let mut merged: BTreeMap<String, Tally> = BTreeMap::new();
+        for (name, tally) in tallies {
+            let name = aliases
+                .get(&name.trim().to_lowercase())
+                .cloned()
+                .unwrap_or(name);
+            merged.entry(name).or_default().add(tally);
+        }
+        merged
+    }
+
+    if project_aliases.is_empty() && tag_aliases.is_empty() {
+        return;
+    }
+    for day in days {
+        day.projects = merge(std::mem::take(&mut day.projects), project_aliases);
+        day.tags = merge(std::mem::take(&mut day.tags), tag_aliases);
+    }
+}
+
+pub fn build_activity_report(
+    start_date: &str,
+    end_date: &str,
+    days: &[DayAggregate],
+) -> ActivityReport {
+    let mut total = Tally::default();
+    let mut projects: BTreeMap<String, Tally> = BTreeMap::new();
+    let mut tags: BTreeMap<String, Tally> = BTreeMap::new();
+    let mut activity_types: HashMap<String, usize> = HashMap::new();
+    let mut daily_breakdown: HashMap<String, f64> = HashMap::new();
+    let mut months: BTreeMap<String, DayAggregate> = BTreeMap::new();
+    let mut duration_issues = Vec::new();
+
+    for day in days {
+        // Un jour sans entrée n'apparaît pas dans les ventilations
+        if day.total.entries == 0 {
+            continue;
+        }
+
+        total.add(day.total);
+        duration_issues.extend(day.duration_issues.iter().cloned());
+        *daily_breakdown.entry(day.date.clone()).or_insert(0.0) += day.total.hours();
+        for (name, tally) in &day.projects {
+            projects.entry(name.clone()).or_default().add(*tally);
+        }
+        for (name, tally) in &day.tags {
+            tags.entry(name.clone()).or_default().add(*tally);
+        }
+        for (name, count) in &day.activity_types {
+            *activity_types.entry(name.clone()).or_insert(0) += count;
+        }
+
+        // Décomposition par mois (format YYYY-MM)
+        if let Some(month) = day.date.get(0..7) {
+            let month = months.entry(month.to_string()).or_default();
+            month.total.add(day.total);
+            for (name, tally) in &day.projects {
+                month.projects.entry(name.clone()).or_default().add(*tally);
+            }
+            for (name, tally) in &day.tags {
+                month.tags.entry(name.clone()).or_default().add(*tally);
+            }
+        }
+    }
+
+    let monthly_breakdown = months
+        .iter()
+        .map(|(month, aggregate)| (month.clone(), aggregate.total.hours()))
+        .collect();
+
+    let monthly_details = months
+        .into_iter()
+        .map(|(month, aggregate)| MonthlyDetail {
+            month,
+            hours: aggregate.total.hours(),
+            projects: aggregate.projects.keys().cloned().collect(),
+            tags: aggregate.tags.keys().cloned().collect(),
+            project_hours: aggregate
+                .projects
+                .iter()
+                .map(|(name, tally)| (name.clone(), tally.hours()))
+                .collect(),
+            tag_hours: aggregate
+                .tags
+                .iter()
+                .map(|(name, tally)| (name.clone(), tally.hours()))
+                .collect(),
+        })
+        .collect();
+
+    ActivityReport {
+        period_start: start_date.to_string(),
+        period_end: end_date.to_string(),
+        total_entries: total.entries,
+        total_hours: total.hours(),
+        projects_summary: projects
+            .into_iter()
+            .map(|(name, tally)| ProjectSummary {
+                name,
+                entries: tally.entries,
+                hours: tally.hours(),
+                color: DEFAULT_PROJECT_COLOR.to_string(),
+            })
+            .collect(),
+        tags_summary: tags
+            .into_iter()
+            .map(|(name, tally)| TagSummary {
+                name,
+                count: tally.entries,
+                color: DEFAULT_TAG_COLOR.to_string(),
+            })
+            .collect(),
+        activity_types,
+        daily_breakdown,
+        monthly_breakdown,
+        monthly_details,
+        duration_issues,
+    }
+}
+
+// Dernier rapport calculé, réutilisé tant que l'index n'a pas changé (par
+// exemple pour l'export DOCX qui suit l'affichage du même rapport)
+#[derive(Default)]
+pub struct ReportCache {
+    last: Option<(u64, String, String, ActivityReport)>,
+}
+
+impl ReportCache {
+    // À appeler quand le rapport change sans que l'index change, par exemple
+    // après l'ajout d'un alias
+    pub fn clear(&mut self) {
+        self.last = None;
+    }
+
+    pub fn get_or_build(
+        &mut self,
+        generation: u64,
+        start_date: &str,
+        end_date: &str,
+        build: impl FnOnce() -> Result<ActivityReport>,
+    ) -> Result<ActivityReport> {
+        if let Some((cached_generation, cached_start, cached_end, report)) = &self.last {
+            if *cached_generation == generation
+                && cached_start == start_date
+                && cached_end == end_date
+            {
+                return Ok(report.clone());
+            }
+        }
+
+        let report = build()?;
+        self.last = Some((
+            generation,
+            start_date.to_string(),
+            end_date.to_string(),
+            report.clone(),
+        ));
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_aliases, build_activity_report, DayAggregate, ReportCache};
+    use crate::duration::EntryDuration;
+    use crate::markdown::ParsedJournalEntry;
+    use crate::timestamp::TimestampFormat;
+    use chrono::{Local, TimeZone};
+
+    fn entry(project: &str, duration: &str, tags: &[&str]) -> ParsedJournalEntry {
+        ParsedJournalEntry {
+            project: project.to_string(),
+            duration: EntryDuration::from_text(duration),
+            entry_type: "development".to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..ParsedJournalEntry::default()
+        }
+    }
+
+    #[test]
+    fn sums_day_aggregates_into_a_report() {
+        let days = [
+            DayAggregate::for_file(
+                "2026-03-31",
+                &[
+                    entry("Mandate", "2h", &["bug"]),
+                    entry("Claims", "30min", &[]),
+                ],
+            ),
+            DayAggregate::for_file("2026-04-01", &[entry("Mandate", "1h30", &["bug"])]),
+            DayAggregate::for_file("2026-04-02", &[]),
+            DayAggregate::for_file("2026-04-03", &[entry("Claims", "5 minutes", &[])]),
+        ]
+        .concat();
+
+        let report = build_activity_report("2026-03-01", "2026-04-30", &days);
+
+        assert_eq!(report.total_entries, 4);
+        assert_eq!(report.total_hours, 4.0);
+        assert_eq!(report.daily_breakdown.len(), 3);
+        assert_eq!(report.duration_issues.len(), 1);
+        assert_eq!(report.duration_issues[0].value, "5 minutes");
+        assert_eq!(report.monthly_breakdown["2026-03"], 2.5);
+        assert_eq!(report.activity_types["development"], 4);
+
+        let mandate = &report.projects_summary[1];
+        assert_eq!((mandate.name.as_str(), mandate.entries), ("Mandate", 2));
+        assert_eq!(mandate.hours, 3.5);
+        assert_eq!(report.tags_summary[0].count, 2);
+
+        let april = &report.monthly_details[1];
+        assert_eq!(april.month, "2026-04");
+        assert_eq!(april.projects, vec!["Claims", "Mandate"]);
+        assert_eq!(april.tag_hours["bug"], 1.5);
+    }
+
+    #[test]
+    fn reports_old_names_under_their_current_name() {
+        let mut days = [
+            DayAggregate::for_file("2026-04-01", &[entry("Mandat", "1h", &["bugfix"])]),
+            DayAggregate::for_file("2026-04-02", &[entry("Mandate", "30min", &["bug"])]),
+        ]
+        .concat();
+        let projects = [("mandat".to_string(), "Mandate".to_string())].into();
+        let tags = [("bugfix".to_string(), "bug".to_string())].into();
+        apply_aliases(&mut days, &projects, &tags);
+
+        let report = build_activity_report("2026-04-01", "2026-04-30", &days);
+        assert_eq!(report.projects_summary.len(), 1);
+        assert_eq!(report.projects_summary[0].name, "Mandate");
+        assert_eq!(report.projects_summary[0].hours, 1.5);
+        assert_eq!(report.tags_summary.len(), 1);
+        assert_eq!(report.tags_summary[0].count, 2);
+    }
+
+    #[test]
+    fn buckets_entries_by_local_day() {
+        let at = |day: u32, hour: u32, minute: u32| {
+            TimestampFormat::Iso8601.format(
+                &Local
+                    .with_ymd_and_hms(2026, 4, day, hour, minute, 0)
+                    .unwrap(),
+            )
+        };
+        let late = ParsedJournalEntry {
+            timestamp: at(7, 0, 30),
+            ..entry("Mandate", "30min", &[])
+        };
+        let next_morning = ParsedJournalEntry {
+            timestamp: at(7, 8, 0),
+            ..entry("Mandate", "45min", &[])
+        };
+        let back_dated = ParsedJournalEntry {
+            timestamp: at(20, 9, 0),
+            ..entry("Mandate", "1h", &[])
+        };
+
+        let days = DayAggregate::for_file("2026-04-06", &[late, next_morning, back_dated]);
+
+        let dates: Vec<(&str, usize)> = days
+            .iter()
+            .map(|day| (day.date.as_str(), day.total.entries))
+            .collect();
+        assert_eq!(dates, vec![("2026-04-06", 2), ("2026-04-07", 1)]);
+    }
+
+    #[test]
+    fn reuses_the_cached_report_until_the_index_changes() {
+        let mut cache = ReportCache::default();
+        let mut builds = 0;
+        let mut build = |generation| {
+            cache
+                .get_or_build(generation, "2026-01-01", "2026-12-31", || {
+                    builds += 1;
+                    Ok(build_activity_report("2026-01-01", "2026-12-31", &[]))
+                })
+                .unwrap()
+        };
+
+        build(1);
+        build(1);
+        build(2);
+        assert_eq!(builds, 2);
+    }
+}
@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+
+use crate::markdown::ParsedJournalEntry;
+
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+// Champs indexés et leur poids dans le classement. L'ordre de FIELDS est
+// celui des colonnes de la table FTS de l'index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    JiraKeys,
+    Tags,
+    Project,
+    Description,
+    Results,
+    Blockers,
+    Reflections,
+}
+
+pub const FIELDS: [Field; 7] = [
+    Field::JiraKeys,
+    Field::Tags,
+    Field::Project,
+    Field::Description,
+    Field::Results,
+    Field::Blockers,
+    Field::Reflections,
+];
+
+impl Field {
+    pub fn weight(self) -> f64 {
+        match self {
+            Field::JiraKeys => 3.0,
+            Field::Tags | Field::Project => 2.0,
+            Field::Description => 1.5,
+            Field::Results | Field::Blockers => 1.0,
+            Field::Reflections => 0.8,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::JiraKeys => "jira_tickets",
+            Field::Tags => "tags",
+            Field::Project => "project",
+            Field::Description => "description",
+            Field::Results => "results",
+            Field::Blockers => "blockers",
+            Field::Reflections => "reflections",
+        }
+    }
+
+    pub fn text(self, entry: &ParsedJournalEntry) -> String {
+        match self {
+            Field::JiraKeys => entry
+                .jira_tickets
+                .iter()
+                .map(|ticket| match &ticket.summary {
+                    Some(summary) => format!("{} {}", ticket.key, summary),
+                    None => ticket.key.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            Field::Tags => entry.tags.join(" "),
+            Field::Project => entry.project.clone(),
+            Field::Description => entry.description.clone(),
+            Field::Results => entry.results.clone(),
+            Field::Blockers => entry.blockers.clone(),
+            Field::Reflections => entry.reflections.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub text: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    pub entry_type: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    pub date: String,
+    pub entry: ParsedJournalEntry,
+    pub score: f64,
+    // Champ d'où provient l'extrait
+    pub field: String,
+    pub snippet: Vec<SnippetPart>,
+}
+
+// Minuscules sans accents, pour que "reunion" trouve "Réunion"
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => folded.push('a'),
+            'ç' => folded.push('c'),
+            'è' | 'é' | 'ê' | 'ë' => folded.push('e'),
+            'ì' | 'í' | 'î' | 'ï' => folded.push('i'),
+            'ñ' => folded.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => folded.push('o'),
+            'ù' | 'ú' | 'û' | 'ü' => folded.push('u'),
+            'ý' | 'ÿ' => folded.push('y'),
+            'œ' => folded.push_str("oe"),
+            'æ' => folded.push_str("ae"),
+            _ => folded.push(c),
+        }
+    }
+    folded
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Requête FTS5 : chaque terme doit apparaître (ET implicite), en préfixe.
+// Les termes issus de `tokenize` sont alphanumériques, les guillemets suffisent.
+pub fn fts_query(terms: &[String]) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+
+    Some(
+        terms
+            .iter()
+            .map(|term| format!("\"{}\"*", term))
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+// Extrait mis en évidence, pris dans le champ le plus pondéré qui contient un
+// des termes recherchés
+pub fn snippet_for(
+    entry: &ParsedJournalEntry,
+    query_terms: &[String],
+) -> (Field, Vec<SnippetPart>) {
+    let field = FIELDS
+        .iter()
+        .copied()
+        .filter(|field| {
+            tokenize(&field.text(entry)).iter().any(|token| {
+                query_terms
+                    .iter()
+                    .any(|term| token.starts_with(term.as_str()))
+            })
+        })
+        .max_by(|a, b| a.weight().total_cmp(&b.weight()))
+        .unwrap_or(if entry.description.is_empty() {
+            Field::Project
+        } else {
+            Field::Description
+        });
+
+    (field, build_snippet(&field.text(entry), query_terms))
+}
+
+fn build_snippet(text: &str, query_terms: &[String]) -> Vec<SnippetPart> {
+    let chars: Vec<char> = text.chars().collect();
+
+    // Repérer les mots qui correspondent à un terme de la requête
+    let mut matches = Vec::new();
+    let mut start = None;
+    for (index, c) in chars.iter().chain(std::iter::once(&' ')).enumerate() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                let word: String = chars[word_start..index].iter().collect();
+                let folded = fold(&word);
+                if query_terms
+                    .iter()
+                    .any(|term| folded.starts_with(term.as_str()))
+                {
+                    matches.push((word_start, index));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    let first_match = matches.first().map(|(start, _)| *start).unwrap_or(0);
+    let window_start = first_match.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let window_end = (first_match + SNIPPET_CONTEXT_CHARS * 2).min(chars.len());
+
+    let mut parts = Vec::new();
+    let mut push = |text: String, highlighted: bool| {
+        if !text.is_empty() {
+            parts.push(SnippetPart { text, highlighted });
+        }
+    };
+
+    let mut cursor = window_start;
+    if window_start > 0 {
+        push("…".to_string(), false);
+    }
+    for (match_start, match_end) in matches {
+        if match_start < cursor || match_end > window_end {
+            continue;
+        }
+        push(chars[cursor..match_start].iter().collect(), false);
+        push(chars[match_start..match_end].iter().collect(), true);
+        cursor = match_end;
+    }
+    push(chars[cursor..window_end].iter().collect(), false);
+    if window_end < chars.len() {
+        push("…".to_string(), false);
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_snippet, fts_query, snippet_for, tokenize, Field, SnippetPart};
+    use crate::markdown::ParsedJournalEntry;
+
+    #[test]
+    fn folds_accents_and_builds_prefix_queries() {
+        let terms = tokenize("Réunion d'équipe");
+        assert_eq!(terms, vec!["reunion", "d", "equipe"]);
+        assert_eq!(
+            fts_query(&terms).as_deref(),
+            Some("\"reunion\"* \"d\"* \"equipe\"*")
+        );
+        assert_eq!(fts_query(&[]), None);
+    }
+
+    #[test]
+    fn picks_the_heaviest_matching_field_for_the_snippet() {
+        let entry = ParsedJournalEntry {
+            project: "Mandate".to_string(),
+            description: "Export des mandats".to_string(),
+            tags: vec!["export".to_string()],
+            ..ParsedJournalEntry::default()
+        };
+
+        let (field, _) = snippet_for(&entry, &tokenize("export"));
+        assert_eq!(field, Field::Tags);
+
+        let (field, snippet) = snippet_for(&entry, &tokenize("mandats"));
+        assert_eq!(field, Field::Description);
+        assert!(snippet
+            .iter()
+            .any(|part| part.highlighted && part.text == "mandats"));
+    }
+
+    #[test]
+    fn highlights_matching_words_in_snippet() {
+        let snippet = build_snippet("Correction de l'Export CSE", &["export".to_string()]);
+
+        assert_eq!(
+            snippet,
+            vec![
+                SnippetPart {
+                    text: "Correction de l'".to_string(),
+                    highlighted: false,
+                },
+                SnippetPart {
+                    text: "Export".to_string(),
+                    highlighted: true,
+                },
+                SnippetPart {
+                    text: " CSE".to_string(),
+                    highlighted: false,
+                },
+            ]
+        );
+    }
+}
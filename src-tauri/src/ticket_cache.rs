@@ -0,0 +1,288 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::jira::JiraTicket;
+
+// Schéma du cache, suivi par `PRAGMA user_version` comme l'index du journal.
+// Le cache peut être supprimé à tout moment : il se remplit à nouveau au fil
+// des recherches et des rafraîchissements.
+const CACHE_MIGRATIONS: &[&str] = &[r#"
+    CREATE TABLE tickets (
+        key TEXT PRIMARY KEY,
+        summary TEXT NOT NULL,
+        status TEXT NOT NULL,
+        issue_type TEXT NOT NULL,
+        updated TEXT,
+        fetched_at INTEGER NOT NULL,
+        missing INTEGER NOT NULL DEFAULT 0,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX tickets_fetched_at ON tickets(fetched_at);
+"#];
+
+// Au-delà, un ticket est relu auprès de Jira par le rafraîchissement
+pub const STALE_AFTER: Duration = Duration::hours(1);
+
+fn migrate(conn: &Connection) -> Result<()> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (index, sql) in CACHE_MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+// Un ticket tel que connu lors de sa dernière lecture
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CachedTicket {
+    #[serde(flatten)]
+    pub ticket: JiraTicket,
+    pub fetched_at: DateTime<Utc>,
+    // Lu il y a plus de `STALE_AFTER`
+    pub stale: bool,
+    // Supprimé ou devenu invisible lors du dernier rafraîchissement
+    pub missing: bool,
+}
+
+// Tickets Jira d'un profil par clé, pour afficher les tickets d'une entrée
+// hors ligne ou absents de la recherche en cours
+pub struct TicketCache {
+    conn: Connection,
+    db_path: PathBuf,
+}
+
+impl TicketCache {
+    pub fn open(db_path: &Path) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Self::init(Connection::open(db_path)?, db_path)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?, Path::new(":memory:"))
+    }
+
+    fn init(conn: Connection, db_path: &Path) -> Result<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        migrate(&conn)?;
+        Ok(Self {
+            conn,
+            db_path: db_path.to_path_buf(),
+        })
+    }
+
+    pub fn is_for(&self, db_path: &Path) -> bool {
+        self.db_path == db_path
+    }
+
+    // Enregistre ou remplace les tickets lus à `now`
+    pub fn store(&mut self, tickets: &[JiraTicket], now: DateTime<Utc>) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for ticket in tickets {
+            tx.execute(
+                "INSERT OR REPLACE INTO tickets
+                    (key, summary, status, issue_type, updated, fetched_at, missing, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7)",
+                params![
+                    ticket.key,
+                    ticket.fields.summary,
+                    ticket.fields.status.name,
+                    ticket.fields.issuetype.name,
+                    ticket.fields.updated,
+                    now.timestamp(),
+                    serde_json::to_string(ticket)?,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // Le ticket n'a pas été trouvé par Jira : la dernière version connue est
+    // gardée pour l'affichage
+    pub fn mark_missing(&self, key: &str, now: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE tickets SET missing = 1, fetched_at = ?2 WHERE key = ?1",
+            params![key, now.timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str, now: DateTime<Utc>) -> Result<Option<CachedTicket>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT data, fetched_at, missing FROM tickets WHERE key = ?1",
+                [key],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, bool>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((data, fetched_at, missing)) = row else {
+            return Ok(None);
+        };
+
+        let fetched_at = DateTime::from_timestamp(fetched_at, 0).unwrap_or_default();
+        Ok(Some(CachedTicket {
+            ticket: serde_json::from_str(&data)?,
+            fetched_at,
+            stale: now - fetched_at > STALE_AFTER,
+            missing,
+        }))
+    }
+
+    // Tickets connus parmi `keys`, dans le même ordre
+    pub fn get_many(&self, keys: &[String], now: DateTime<Utc>) -> Result<Vec<CachedTicket>> {
+        let mut tickets = Vec::new();
+        for key in keys {
+            if let Some(ticket) = self.get(key, now)? {
+                tickets.push(ticket);
+            }
+        }
+        Ok(tickets)
+    }
+
+    // Au plus `limit` tickets à relire, les plus anciens d'abord
+    pub fn stale_keys(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT key FROM tickets WHERE fetched_at < ?1 ORDER BY fetched_at, key LIMIT ?2",
+        )?;
+        let keys = stmt
+            .query_map(
+                params![(now - STALE_AFTER).timestamp(), limit as i64],
+                |row| row.get(0),
+            )?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TicketCache, STALE_AFTER};
+    use crate::jira::{JiraFields, JiraIssueType, JiraStatus, JiraTicket};
+    use chrono::{DateTime, Duration, Utc};
+
+    fn ticket(key: &str, summary: &str) -> JiraTicket {
+        JiraTicket {
+            key: key.to_string(),
+            fields: JiraFields {
+                summary: summary.to_string(),
+                status: JiraStatus {
+                    name: "En cours".to_string(),
+                },
+                issuetype: JiraIssueType {
+                    name: "Tâche".to_string(),
+                },
+                updated: Some("2026-04-06T10:12:00.000+0200".to_string()),
+                ..JiraFields::default()
+            },
+        }
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().to_utc()
+    }
+
+    #[test]
+    fn looks_up_tickets_by_key() {
+        let mut cache = TicketCache::open_in_memory().unwrap();
+        let now = at("2026-04-06T10:00:00Z");
+        cache
+            .store(
+                &[
+                    ticket("OPS-1", "Certificat"),
+                    ticket("OPS-2", "Sauvegardes"),
+                ],
+                now,
+            )
+            .unwrap();
+        cache
+            .store(&[ticket("OPS-1", "Renouveler le certificat")], now)
+            .unwrap();
+
+        let keys = ["OPS-2", "OPS-3", "OPS-1"].map(str::to_string);
+        let tickets = cache.get_many(&keys, now).unwrap();
+
+        let found: Vec<&str> = tickets.iter().map(|t| t.ticket.key.as_str()).collect();
+        assert_eq!(found, vec!["OPS-2", "OPS-1"]);
+        assert_eq!(tickets[1].ticket.fields.summary, "Renouveler le certificat");
+        assert_eq!(
+            tickets[1].ticket.fields.updated.as_deref(),
+            Some("2026-04-06T10:12:00.000+0200")
+        );
+        assert!(!tickets[1].stale);
+    }
+
+    #[test]
+    fn lists_stale_tickets_oldest_first() {
+        let mut cache = TicketCache::open_in_memory().unwrap();
+        let morning = at("2026-04-06T08:00:00Z");
+        cache
+            .store(&[ticket("OPS-2", "Sauvegardes")], morning)
+            .unwrap();
+        cache
+            .store(
+                &[ticket("OPS-1", "Certificat")],
+                morning - Duration::minutes(5),
+            )
+            .unwrap();
+        cache
+            .store(
+                &[ticket("OPS-3", "Supervision")],
+                morning + Duration::hours(2),
+            )
+            .unwrap();
+        let now = morning + STALE_AFTER + Duration::minutes(1);
+
+        assert_eq!(cache.stale_keys(now, 10).unwrap(), vec!["OPS-1", "OPS-2"]);
+        assert_eq!(cache.stale_keys(now, 1).unwrap(), vec!["OPS-1"]);
+        assert!(cache.get("OPS-1", now).unwrap().unwrap().stale);
+
+        // Un ticket introuvable est gardé, marqué, et n'est plus à relire
+        cache.mark_missing("OPS-1", now).unwrap();
+        let missing = cache.get("OPS-1", now).unwrap().unwrap();
+        assert!(missing.missing && !missing.stale);
+        assert_eq!(missing.ticket.fields.summary, "Certificat");
+        assert_eq!(cache.stale_keys(now, 10).unwrap(), vec!["OPS-2"]);
+    }
+
+    #[test]
+    fn survives_a_restart() {
+        let dir = std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
+        let db_path = dir.join("jira-tickets-default.db");
+        let now = Utc::now();
+        TicketCache::open(&db_path)
+            .unwrap()
+            .store(&[ticket("OPS-1", "Certificat")], now)
+            .unwrap();
+
+        let cache = TicketCache::open(&db_path).unwrap();
+
+        assert!(cache.is_for(&db_path));
+        assert_eq!(
+            cache
+                .get("OPS-1", now)
+                .unwrap()
+                .unwrap()
+                .ticket
+                .fields
+                .summary,
+            "Certificat"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
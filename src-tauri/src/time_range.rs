@@ -0,0 +1,263 @@
+use regex::Regex;
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::duration::{Duration, DurationError};
+use crate::markdown::ParsedJournalEntry;
+
+// Au-delà, une journée est signalée comme trop chargée (préférence
+// `max_daily_minutes`)
+pub const DEFAULT_MAX_DAILY_MINUTES: u32 = 10 * 60;
+
+fn time_range_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^(\d{1,2})[:hH](\d{2})\s*(?:-|–|à)\s*(\d{1,2})[:hH](\d{2})$").unwrap()
+    })
+}
+
+// Plage horaire d'une entrée ("09:00-10:30"), en minutes depuis minuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl TimeRange {
+    pub fn parse(input: &str) -> Option<Self> {
+        let captures = time_range_regex().captures(input.trim())?;
+        let minutes = |hours: usize, minutes: usize| -> Option<u32> {
+            let hours: u32 = captures[hours].parse().ok()?;
+            let minutes: u32 = captures[minutes].parse().ok()?;
+            (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+        };
+
+        let start = minutes(1, 2)?;
+        let end = minutes(3, 4)?;
+        // Les plages qui passent minuit ne sont pas gérées
+        (end > start).then_some(Self { start, end })
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_minutes(self.end - self.start)
+    }
+}
+
+fn format_time(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", format_time(self.start), format_time(self.end))
+    }
+}
+
+// Durée d'une entrée : celle saisie, ou à défaut celle de sa plage horaire
+pub fn effective_duration(entry: &ParsedJournalEntry) -> Result<Duration, DurationError> {
+    match (entry.duration.known(), entry.duration.error()) {
+        (Some(duration), _) => Ok(duration),
+        (None, Some(DurationError::Empty)) => TimeRange::parse(&entry.time_range)
+            .map(|range| range.duration())
+            .ok_or(DurationError::Empty),
+        (None, error) => Err(error.unwrap_or(DurationError::Invalid)),
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Overlap {
+    pub first_entry_id: String,
+    pub second_entry_id: String,
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Gap {
+    pub after_entry_id: String,
+    pub before_entry_id: String,
+    pub start: String,
+    pub end: String,
+    pub minutes: u32,
+}
+
+// Durée saisie qui ne correspond pas à la plage horaire
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DurationMismatch {
+    pub entry_id: String,
+    pub time_range: String,
+    pub duration: String,
+    pub range_minutes: u32,
+    pub duration_minutes: u32,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct InvalidTimeRange {
+    pub entry_id: String,
+    pub time_range: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DayValidation {
+    pub date: String,
+    pub entries: usize,
+    pub total_minutes: u32,
+    pub max_minutes: u32,
+    pub exceeds_max: bool,
+    pub overlaps: Vec<Overlap>,
+    pub gaps: Vec<Gap>,
+    pub mismatches: Vec<DurationMismatch>,
+    pub invalid_time_ranges: Vec<InvalidTimeRange>,
+}
+
+pub fn validate_day(date: &str, entries: &[ParsedJournalEntry], max_minutes: u32) -> DayValidation {
+    let mut validation = DayValidation {
+        date: date.to_string(),
+        entries: entries.len(),
+        total_minutes: 0,
+        max_minutes,
+        exceeds_max: false,
+        overlaps: Vec::new(),
+        gaps: Vec::new(),
+        mismatches: Vec::new(),
+        invalid_time_ranges: Vec::new(),
+    };
+
+    let mut ranges: Vec<(TimeRange, &ParsedJournalEntry)> = Vec::new();
+    for entry in entries {
+        if let Ok(duration) = effective_duration(entry) {
+            validation.total_minutes += duration.minutes();
+        }
+
+        if entry.time_range.trim().is_empty() {
+            continue;
+        }
+        let Some(range) = TimeRange::parse(&entry.time_range) else {
+            validation.invalid_time_ranges.push(InvalidTimeRange {
+                entry_id: entry.id.clone(),
+                time_range: entry.time_range.clone(),
+            });
+            continue;
+        };
+
+        if let Some(duration) = entry.duration.known() {
+            if duration != range.duration() {
+                validation.mismatches.push(DurationMismatch {
+                    entry_id: entry.id.clone(),
+                    time_range: entry.time_range.clone(),
+                    duration: duration.to_string(),
+                    range_minutes: range.duration().minutes(),
+                    duration_minutes: duration.minutes(),
+                });
+            }
+        }
+        ranges.push((range, entry));
+    }
+
+    ranges.sort_by_key(|(range, _)| (range.start, range.end));
+
+    // Chevauchements entre toutes les paires, trous entre la fin la plus
+    // tardive atteinte et le début suivant
+    for (index, (range, entry)) in ranges.iter().enumerate() {
+        for (other, other_entry) in &ranges[index + 1..] {
+            if other.start >= range.end {
+                break;
+            }
+            validation.overlaps.push(Overlap {
+                first_entry_id: entry.id.clone(),
+                second_entry_id: other_entry.id.clone(),
+                start: format_time(other.start),
+                end: format_time(range.end.min(other.end)),
+            });
+        }
+    }
+
+    let mut latest: Option<(u32, &ParsedJournalEntry)> = None;
+    for (range, entry) in &ranges {
+        if let Some((end, previous)) = latest {
+            if range.start > end {
+                validation.gaps.push(Gap {
+                    after_entry_id: previous.id.clone(),
+                    before_entry_id: entry.id.clone(),
+                    start: format_time(end),
+                    end: format_time(range.start),
+                    minutes: range.start - end,
+                });
+            }
+        }
+        if latest.is_none_or(|(end, _)| range.end > end) {
+            latest = Some((range.end, entry));
+        }
+    }
+
+    validation.exceeds_max = validation.total_minutes > max_minutes;
+    validation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{effective_duration, validate_day, TimeRange};
+    use crate::duration::{Duration, EntryDuration};
+    use crate::markdown::ParsedJournalEntry;
+
+    fn entry(id: &str, time_range: &str, duration: &str) -> ParsedJournalEntry {
+        ParsedJournalEntry {
+            id: id.to_string(),
+            time_range: time_range.to_string(),
+            duration: EntryDuration::from_text(duration),
+            ..ParsedJournalEntry::default()
+        }
+    }
+
+    #[test]
+    fn parses_time_ranges() {
+        let range = TimeRange::parse("09:00-10:30").unwrap();
+        assert_eq!(range.duration(), Duration::from_minutes(90));
+        assert_eq!(
+            TimeRange::parse("9h15 - 9h45").unwrap().to_string(),
+            "09:15-09:45"
+        );
+        assert_eq!(TimeRange::parse("10:00-09:00"), None);
+        assert_eq!(TimeRange::parse("25:00-26:00"), None);
+        assert_eq!(TimeRange::parse("matin"), None);
+    }
+
+    #[test]
+    fn derives_missing_duration_from_time_range() {
+        assert_eq!(
+            effective_duration(&entry("a", "09:00-09:45", "")),
+            Ok(Duration::from_minutes(45))
+        );
+        assert!(effective_duration(&entry("a", "", "")).is_err());
+        assert!(effective_duration(&entry("a", "09:00-09:45", "5")).is_err());
+    }
+
+    #[test]
+    fn reports_overlaps_gaps_mismatches_and_overload() {
+        let entries = vec![
+            entry("a", "09:00-10:30", "1h30"),
+            entry("b", "10:00-11:00", "2h"),
+            entry("c", "13:00-14:00", ""),
+            entry("d", "midi", "30min"),
+        ];
+
+        let validation = validate_day("2026-04-06", &entries, 4 * 60);
+
+        assert_eq!(validation.total_minutes, 90 + 120 + 60 + 30);
+        assert!(validation.exceeds_max);
+        assert_eq!(validation.overlaps.len(), 1);
+        assert_eq!(
+            (
+                validation.overlaps[0].start.as_str(),
+                validation.overlaps[0].end.as_str()
+            ),
+            ("10:00", "10:30")
+        );
+        assert_eq!(validation.gaps.len(), 1);
+        assert_eq!(validation.gaps[0].minutes, 120);
+        assert_eq!(validation.mismatches.len(), 1);
+        assert_eq!(validation.mismatches[0].entry_id, "b");
+        assert_eq!(validation.invalid_time_ranges.len(), 1);
+    }
+}
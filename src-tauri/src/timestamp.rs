@@ -0,0 +1,204 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+// Préférence choisissant l'écriture de l'horodatage des nouvelles entrées
+pub const TIMESTAMP_FORMAT_PREFERENCE: &str = "timestamp_format";
+
+// Ancien horodatage, écrit en UTC sans fuseau
+const LEGACY_FORMAT: &str = "%d/%m/%Y %H:%M";
+
+// Toutes les variantes portent un décalage explicite pour pouvoir être relues
+// sans ambiguïté
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    // 2026-04-06T09:00:00+02:00
+    #[default]
+    Iso8601,
+    // 2026-04-06T09:00+02:00
+    Iso8601Minutes,
+    // 06/04/2026 09:00 +02:00
+    French,
+}
+
+impl TimestampFormat {
+    pub fn from_preference(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some("iso8601_minutes") => TimestampFormat::Iso8601Minutes,
+            Some("french") => TimestampFormat::French,
+            _ => TimestampFormat::Iso8601,
+        }
+    }
+
+    fn pattern(self) -> &'static str {
+        match self {
+            TimestampFormat::Iso8601 => "%Y-%m-%dT%H:%M:%S%:z",
+            TimestampFormat::Iso8601Minutes => "%Y-%m-%dT%H:%M%:z",
+            TimestampFormat::French => "%d/%m/%Y %H:%M %:z",
+        }
+    }
+
+    pub fn format<Tz: TimeZone>(self, at: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        at.format(self.pattern()).to_string()
+    }
+}
+
+// Horodatage d'une nouvelle entrée pour la journée `date` : l'heure locale
+// courante, ramenée sur la journée choisie quand l'entrée est saisie après coup
+pub fn entry_timestamp(date: &str, format: TimestampFormat) -> String {
+    let now = Local::now();
+    let Ok(day) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return format.format(&now);
+    };
+    if day == now.date_naive() {
+        return format.format(&now);
+    }
+
+    match Local
+        .from_local_datetime(&day.and_time(now.time()))
+        .earliest()
+    {
+        Some(at) => format.format(&at),
+        None => format.format(&now),
+    }
+}
+
+// Relit un horodatage, quel que soit le format qui l'a écrit. Les anciens
+// horodatages sans fuseau sont en UTC ; un titre réduit à l'heure ("09:00")
+// est rattaché à la journée du fichier, en heure locale.
+pub fn parse_timestamp(text: &str, file_date: &str) -> Option<DateTime<FixedOffset>> {
+    let text = text.trim();
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Some(at);
+    }
+    for format in [TimestampFormat::Iso8601Minutes, TimestampFormat::French] {
+        if let Ok(at) = DateTime::parse_from_str(text, format.pattern()) {
+            return Some(at);
+        }
+    }
+    if let Ok(naive) = NaiveDateTime::parse_from_str(text, LEGACY_FORMAT) {
+        return Some(Utc.from_utc_datetime(&naive).fixed_offset());
+    }
+
+    let time = NaiveTime::parse_from_str(text, "%H:%M").ok()?;
+    let day = NaiveDate::parse_from_str(file_date, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&day.and_time(time))
+        .earliest()
+        .map(|at| at.fixed_offset())
+}
+
+// Marge autour de minuit dans laquelle l'horodatage d'une entrée peut la
+// faire changer de journée
+const MIDNIGHT_MARGIN_HOURS: i64 = 3;
+
+// Journée locale d'une entrée. L'horodatage ne l'emporte sur le nom du fichier
+// que s'il tombe à quelques heures de minuit autour de cette journée (saisie
+// juste après minuit, anciens horodatages UTC). Sinon, c'est une entrée saisie
+// après coup, le lendemain matin par exemple, et le fichier fait foi.
+pub fn entry_local_day(timestamp: &str, file_date: &str) -> String {
+    let Ok(file_day) = NaiveDate::parse_from_str(file_date, "%Y-%m-%d") else {
+        return file_date.to_string();
+    };
+    let Some(at) = parse_timestamp(timestamp, file_date) else {
+        return file_date.to_string();
+    };
+
+    let local = at.with_timezone(&Local).naive_local();
+    let margin = chrono::Duration::hours(MIDNIGHT_MARGIN_HOURS);
+    let start = file_day.and_time(NaiveTime::MIN);
+    let end = start + chrono::Duration::days(1);
+    if local >= start - margin && local < end + margin {
+        local.date().format("%Y-%m-%d").to_string()
+    } else {
+        file_date.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_local_day, entry_timestamp, parse_timestamp, TimestampFormat};
+    use chrono::{FixedOffset, Local, TimeZone};
+
+    #[test]
+    fn formats_with_an_explicit_offset() {
+        let at = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2026, 4, 6, 9, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            TimestampFormat::Iso8601.format(&at),
+            "2026-04-06T09:00:00+02:00"
+        );
+        assert_eq!(
+            TimestampFormat::Iso8601Minutes.format(&at),
+            "2026-04-06T09:00+02:00"
+        );
+        assert_eq!(
+            TimestampFormat::French.format(&at),
+            "06/04/2026 09:00 +02:00"
+        );
+
+        for format in [
+            TimestampFormat::Iso8601,
+            TimestampFormat::Iso8601Minutes,
+            TimestampFormat::French,
+        ] {
+            assert_eq!(parse_timestamp(&format.format(&at), "2026-04-06"), Some(at));
+        }
+    }
+
+    #[test]
+    fn reads_legacy_timestamps_as_utc() {
+        let at = parse_timestamp("05/04/2026 22:30", "2026-04-06").unwrap();
+        assert_eq!(at.offset().local_minus_utc(), 0);
+        assert_eq!(at.to_rfc3339(), "2026-04-05T22:30:00+00:00");
+        assert!(parse_timestamp("n'importe quoi", "2026-04-06").is_none());
+    }
+
+    #[test]
+    fn stamps_back_dated_entries_on_their_day() {
+        let timestamp = entry_timestamp("2020-02-03", TimestampFormat::Iso8601);
+        assert!(timestamp.starts_with("2020-02-03T"));
+        assert_eq!(entry_local_day(&timestamp, "2020-02-03"), "2020-02-03");
+    }
+
+    #[test]
+    fn keeps_the_file_day_for_entries_stamped_much_later() {
+        assert_eq!(
+            entry_local_day("2026-04-10T09:00:00+02:00", "2026-04-06"),
+            "2026-04-06"
+        );
+        assert_eq!(entry_local_day("09:00", "2026-04-06"), "2026-04-06");
+        assert_eq!(entry_local_day("Réunion", "2026-04-06"), "2026-04-06");
+    }
+
+    #[test]
+    fn moves_entries_only_when_stamped_around_midnight() {
+        let local = |day: u32, hour: u32, minute: u32| {
+            let at = Local
+                .with_ymd_and_hms(2026, 4, day, hour, minute, 0)
+                .unwrap();
+            TimestampFormat::Iso8601.format(&at)
+        };
+
+        // Saisie juste après minuit, ou juste avant dans le fichier du lendemain
+        assert_eq!(
+            entry_local_day(&local(7, 0, 30), "2026-04-06"),
+            "2026-04-07"
+        );
+        assert_eq!(
+            entry_local_day(&local(6, 23, 15), "2026-04-07"),
+            "2026-04-06"
+        );
+        // Journée rattrapée le lendemain matin
+        assert_eq!(entry_local_day(&local(7, 7, 0), "2026-04-06"), "2026-04-06");
+        assert_eq!(
+            entry_local_day(&local(5, 18, 0), "2026-04-06"),
+            "2026-04-06"
+        );
+    }
+}
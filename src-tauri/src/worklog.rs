@@ -0,0 +1,495 @@
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::file_manager::set_entry_worklogs;
+use crate::jira::{JiraClient, Worklog};
+use crate::markdown::{ParsedJournalEntry, WorklogRef};
+use crate::time_range::{effective_duration, TimeRange};
+use crate::timestamp::parse_timestamp;
+
+// Début retenu pour une entrée sans plage horaire ni horodatage lisible
+const DEFAULT_START_HOUR: u32 = 9;
+
+// Répartition de la durée d'une entrée liée à plusieurs tickets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitRule {
+    // À parts égales, les minutes restantes allant aux premiers tickets
+    #[default]
+    Even,
+    // Tout sur le premier ticket lié
+    FirstTicket,
+    // La durée complète sur chaque ticket
+    FullEach,
+}
+
+pub fn split_minutes(total: u32, tickets: usize, rule: SplitRule) -> Vec<u32> {
+    let count = tickets as u32;
+    match rule {
+        SplitRule::Even if count > 0 => (0..count)
+            .map(|index| total / count + u32::from(index < total % count))
+            .collect(),
+        SplitRule::Even => Vec::new(),
+        SplitRule::FirstTicket => (0..tickets)
+            .map(|index| if index == 0 { total } else { 0 })
+            .collect(),
+        SplitRule::FullEach => vec![total; tickets],
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorklogAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PlannedWorklog {
+    pub date: String,
+    pub entry_id: String,
+    pub issue_key: String,
+    pub action: WorklogAction,
+    pub worklog_id: Option<String>,
+    pub started: DateTime<FixedOffset>,
+    pub minutes: u32,
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SkippedEntry {
+    pub date: String,
+    pub entry_id: String,
+    pub timestamp: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FailedWorklog {
+    pub worklog: PlannedWorklog,
+    pub error: String,
+}
+
+// Worklogs envoyés à Jira dont l'identifiant n'a pas pu être noté dans
+// l'entrée : sans correction, la prochaine synchronisation les dupliquera
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct UnsavedWorklogRefs {
+    pub date: String,
+    pub entry_id: String,
+    pub worklogs: Vec<WorklogRef>,
+    pub error: String,
+}
+
+// Bilan d'une synchronisation : en simulation, `worklogs` liste ce qui serait
+// envoyé ; sinon, ce qui l'a été
+#[derive(Debug, Serialize, Default)]
+pub struct WorklogSync {
+    pub dry_run: bool,
+    pub worklogs: Vec<PlannedWorklog>,
+    pub skipped: Vec<SkippedEntry>,
+    pub failed: Vec<FailedWorklog>,
+    pub unsaved: Vec<UnsavedWorklogRefs>,
+}
+
+fn local_time(day: NaiveDate, minutes: u32) -> Option<DateTime<FixedOffset>> {
+    let time = NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0)?;
+    Local
+        .from_local_datetime(&day.and_time(time))
+        .earliest()
+        .map(|at| at.fixed_offset())
+}
+
+// Début du travail : celui de la plage horaire, à défaut l'horodatage de
+// l'entrée
+fn entry_start(date: &str, entry: &ParsedJournalEntry) -> Option<DateTime<FixedOffset>> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    if let Some(range) = TimeRange::parse(&entry.time_range) {
+        return local_time(day, range.start);
+    }
+    parse_timestamp(&entry.timestamp, date).or_else(|| local_time(day, DEFAULT_START_HOUR * 60))
+}
+
+fn worklog_comment(entry: &ParsedJournalEntry) -> String {
+    match entry.description.trim() {
+        "" => entry.project.trim().to_string(),
+        description => description.to_string(),
+    }
+}
+
+// Worklogs à créer, mettre à jour ou supprimer pour une entrée. Un worklog déjà
+// synchronisé dont le ticket n'est plus lié (ou ne reçoit plus de temps) est
+// supprimé.
+pub fn plan_entry(
+    date: &str,
+    entry: &ParsedJournalEntry,
+    rule: SplitRule,
+) -> Result<Vec<PlannedWorklog>, String> {
+    let mut keys: Vec<&str> = Vec::new();
+    for ticket in &entry.jira_tickets {
+        if !keys.contains(&ticket.key.as_str()) {
+            keys.push(&ticket.key);
+        }
+    }
+
+    let minutes = if keys.is_empty() {
+        0
+    } else {
+        effective_duration(entry)
+            .map_err(|e| e.to_string())?
+            .minutes()
+    };
+    let mut started =
+        entry_start(date, entry).ok_or_else(|| format!("Date invalide : {}", date))?;
+    let comment = worklog_comment(entry);
+
+    let planned = |issue_key: &str, action, worklog_id: Option<&WorklogRef>, started, minutes| {
+        PlannedWorklog {
+            date: date.to_string(),
+            entry_id: entry.id.clone(),
+            issue_key: issue_key.to_string(),
+            action,
+            worklog_id: worklog_id.map(|worklog| worklog.worklog_id.clone()),
+            started,
+            minutes,
+            comment: comment.clone(),
+        }
+    };
+
+    let mut existing: Vec<&WorklogRef> = entry.worklogs.iter().collect();
+    let mut worklogs = Vec::new();
+    for (key, share) in keys.iter().zip(split_minutes(minutes, keys.len(), rule)) {
+        if share == 0 {
+            continue;
+        }
+        let found = existing
+            .iter()
+            .position(|worklog| worklog.issue_key == *key)
+            .map(|index| existing.remove(index));
+        let action = match found {
+            Some(_) => WorklogAction::Update,
+            None => WorklogAction::Create,
+        };
+        worklogs.push(planned(key, action, found, started, share));
+
+        // Les parts d'une même entrée se suivent au lieu de se chevaucher
+        if rule == SplitRule::Even {
+            started += chrono::Duration::minutes(share as i64);
+        }
+    }
+    for worklog in existing {
+        worklogs.push(planned(
+            &worklog.issue_key,
+            WorklogAction::Delete,
+            Some(worklog),
+            started,
+            0,
+        ));
+    }
+
+    Ok(worklogs)
+}
+
+// Prépare la synchronisation des entrées des jours donnés, ou des seules
+// entrées `entry_ids` parmi celles-ci
+pub fn plan_worklogs(
+    days: &[(String, Vec<ParsedJournalEntry>)],
+    entry_ids: Option<&[String]>,
+    rule: SplitRule,
+) -> WorklogSync {
+    let mut sync = WorklogSync {
+        dry_run: true,
+        ..WorklogSync::default()
+    };
+
+    for (date, entries) in days {
+        for entry in entries {
+            if entry_ids.is_some_and(|ids| !ids.contains(&entry.id)) {
+                continue;
+            }
+
+            let reason = if entry.jira_tickets.is_empty() && entry.worklogs.is_empty() {
+                // Sur une période, les entrées sans ticket sont simplement ignorées
+                entry_ids.map(|_| "Aucun ticket Jira lié".to_string())
+            } else {
+                match plan_entry(date, entry, rule) {
+                    Ok(worklogs) => {
+                        sync.worklogs.extend(worklogs);
+                        None
+                    }
+                    Err(reason) => Some(reason),
+                }
+            };
+
+            if let Some(reason) = reason {
+                sync.skipped.push(SkippedEntry {
+                    date: date.clone(),
+                    entry_id: entry.id.clone(),
+                    timestamp: entry.timestamp.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+
+    sync
+}
+
+async fn apply_worklog(
+    client: &JiraClient,
+    planned: &mut PlannedWorklog,
+    refs: &mut Vec<WorklogRef>,
+) -> Result<()> {
+    let worklog = Worklog {
+        started: planned.started,
+        minutes: planned.minutes,
+        comment: planned.comment.clone(),
+    };
+    let existing_id = planned.worklog_id.clone().unwrap_or_default();
+
+    match planned.action {
+        WorklogAction::Create => {
+            let id = client.add_worklog(&planned.issue_key, &worklog).await?;
+            refs.push(WorklogRef {
+                issue_key: planned.issue_key.clone(),
+                worklog_id: id.clone(),
+            });
+            planned.worklog_id = Some(id);
+        }
+        WorklogAction::Update => {
+            let updated = client
+                .update_worklog(&planned.issue_key, &existing_id, &worklog)
+                .await?;
+            // Supprimé dans Jira entre-temps : il est recréé
+            if !updated {
+                let id = client.add_worklog(&planned.issue_key, &worklog).await?;
+                for worklog_ref in refs.iter_mut() {
+                    if worklog_ref.worklog_id == existing_id {
+                        worklog_ref.worklog_id = id.clone();
+                    }
+                }
+                planned.action = WorklogAction::Create;
+                planned.worklog_id = Some(id);
+            }
+        }
+        WorklogAction::Delete => {
+            client
+                .delete_worklog(&planned.issue_key, &existing_id)
+                .await?;
+            refs.retain(|worklog_ref| worklog_ref.worklog_id != existing_id);
+        }
+    }
+    Ok(())
+}
+
+// Envoie les worklogs prévus puis enregistre leurs identifiants dans les
+// entrées. Un worklog en échec n'empêche pas l'envoi des suivants, et un
+// fichier du journal impossible à réécrire n'efface pas le bilan des envois.
+pub async fn push_worklogs(
+    client: &JiraClient,
+    journal_dir: &Path,
+    plan: WorklogSync,
+) -> WorklogSync {
+    // Worklogs connus de chaque entrée avant l'envoi : tous figurent dans le
+    // plan, à mettre à jour ou à supprimer
+    let mut refs: BTreeMap<(String, String), Vec<WorklogRef>> = BTreeMap::new();
+    for planned in &plan.worklogs {
+        let entry_refs = refs
+            .entry((planned.date.clone(), planned.entry_id.clone()))
+            .or_default();
+        if let Some(worklog_id) = &planned.worklog_id {
+            entry_refs.push(WorklogRef {
+                issue_key: planned.issue_key.clone(),
+                worklog_id: worklog_id.clone(),
+            });
+        }
+    }
+
+    let mut sync = WorklogSync {
+        dry_run: false,
+        skipped: plan.skipped,
+        ..WorklogSync::default()
+    };
+    for mut planned in plan.worklogs {
+        let entry_refs = refs
+            .entry((planned.date.clone(), planned.entry_id.clone()))
+            .or_default();
+        match apply_worklog(client, &mut planned, entry_refs).await {
+            Ok(()) => sync.worklogs.push(planned),
+            Err(e) => sync.failed.push(FailedWorklog {
+                worklog: planned,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let mut days: BTreeMap<String, HashMap<String, Vec<WorklogRef>>> = BTreeMap::new();
+    for ((date, entry_id), entry_refs) in refs {
+        days.entry(date).or_default().insert(entry_id, entry_refs);
+    }
+    for (date, worklogs) in days {
+        if let Err(e) = set_entry_worklogs(journal_dir, &date, &worklogs) {
+            tracing::warn!("saving worklog ids for {} failed: {}", date, e);
+            sync.unsaved
+                .extend(
+                    worklogs
+                        .into_iter()
+                        .map(|(entry_id, worklogs)| UnsavedWorklogRefs {
+                            date: date.clone(),
+                            entry_id,
+                            worklogs,
+                            error: e.to_string(),
+                        }),
+                );
+        }
+    }
+
+    sync
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        plan_entry, plan_worklogs, push_worklogs, split_minutes, SplitRule, WorklogAction,
+        WorklogSync,
+    };
+    use crate::duration::EntryDuration;
+    use crate::jira::{JiraClient, JiraDeployment};
+    use crate::markdown::{JiraTicketRef, ParsedJournalEntry, WorklogRef};
+
+    fn entry(duration: &str, keys: &[&str], worklogs: &[(&str, &str)]) -> ParsedJournalEntry {
+        ParsedJournalEntry {
+            id: "entry-1".to_string(),
+            description: "Export DOCX".to_string(),
+            time_range: "09:00-10:30".to_string(),
+            duration: EntryDuration::from_text(duration),
+            jira_tickets: keys
+                .iter()
+                .map(|key| JiraTicketRef {
+                    key: key.to_string(),
+                    summary: None,
+                    url: None,
+                })
+                .collect(),
+            worklogs: worklogs
+                .iter()
+                .map(|(issue_key, worklog_id)| WorklogRef {
+                    issue_key: issue_key.to_string(),
+                    worklog_id: worklog_id.to_string(),
+                })
+                .collect(),
+            ..ParsedJournalEntry::default()
+        }
+    }
+
+    #[test]
+    fn splits_minutes_by_rule() {
+        assert_eq!(split_minutes(91, 3, SplitRule::Even), vec![31, 30, 30]);
+        assert_eq!(split_minutes(90, 2, SplitRule::FirstTicket), vec![90, 0]);
+        assert_eq!(split_minutes(90, 2, SplitRule::FullEach), vec![90, 90]);
+        assert!(split_minutes(90, 0, SplitRule::Even).is_empty());
+    }
+
+    #[test]
+    fn updates_synced_worklogs_instead_of_duplicating() {
+        let entry = entry(
+            "1h30",
+            &["CSE-1", "CSE-2"],
+            &[("CSE-1", "100"), ("CSE-9", "900")],
+        );
+
+        let worklogs = plan_entry("2026-04-06", &entry, SplitRule::Even).unwrap();
+
+        let summary: Vec<_> = worklogs
+            .iter()
+            .map(|w| {
+                (
+                    w.issue_key.as_str(),
+                    w.action,
+                    w.worklog_id.as_deref(),
+                    w.minutes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("CSE-1", WorklogAction::Update, Some("100"), 45),
+                ("CSE-2", WorklogAction::Create, None, 45),
+                ("CSE-9", WorklogAction::Delete, Some("900"), 0),
+            ]
+        );
+        assert_eq!(worklogs[0].started.format("%H:%M").to_string(), "09:00");
+        assert_eq!(worklogs[1].started.format("%H:%M").to_string(), "09:45");
+        assert_eq!(worklogs[0].comment, "Export DOCX");
+    }
+
+    #[test]
+    fn skips_entries_whose_duration_is_unknown() {
+        let days = vec![(
+            "2026-04-06".to_string(),
+            vec![
+                ParsedJournalEntry {
+                    time_range: String::new(),
+                    ..entry("5", &["CSE-1"], &[])
+                },
+                ParsedJournalEntry {
+                    id: "entry-2".to_string(),
+                    ..entry("1h", &[], &[])
+                },
+            ],
+        )];
+
+        let sync = plan_worklogs(&days, None, SplitRule::Even);
+        assert!(sync.dry_run);
+        assert!(sync.worklogs.is_empty());
+        assert_eq!(sync.skipped.len(), 1);
+        assert_eq!(sync.skipped[0].entry_id, "entry-1");
+
+        let ids = vec!["entry-2".to_string()];
+        let sync = plan_worklogs(&days, Some(&ids), SplitRule::Even);
+        assert_eq!(sync.skipped[0].reason, "Aucun ticket Jira lié");
+    }
+
+    #[tokio::test]
+    async fn reports_worklog_ids_it_could_not_save() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/rest/api/3/issue/CSE-1/worklog")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "id": "10042" }"#)
+            .create_async()
+            .await;
+
+        // Un dossier à la place du fichier du jour : la réécriture échoue
+        let journal_dir =
+            std::env::temp_dir().join(format!("dev-journal-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(journal_dir.join("2026/04/2026-04-06.md")).unwrap();
+
+        let client = JiraClient::new(server.url(), "moi@acme.com".into(), "token".into())
+            .with_deployment(JiraDeployment::Cloud);
+        let plan = WorklogSync {
+            worklogs: plan_entry("2026-04-06", &entry("1h", &["CSE-1"], &[]), SplitRule::Even)
+                .unwrap(),
+            ..WorklogSync::default()
+        };
+        let sync = push_worklogs(&client, &journal_dir, plan).await;
+        std::fs::remove_dir_all(&journal_dir).unwrap();
+
+        assert!(sync.failed.is_empty());
+        assert_eq!(sync.worklogs[0].worklog_id.as_deref(), Some("10042"));
+        assert_eq!(sync.unsaved.len(), 1);
+        assert_eq!(sync.unsaved[0].entry_id, "entry-1");
+        assert_eq!(
+            sync.unsaved[0].worklogs,
+            vec![WorklogRef {
+                issue_key: "CSE-1".to_string(),
+                worklog_id: "10042".to_string(),
+            }]
+        );
+    }
+}
@@ -5,11 +5,21 @@ import JournalEntriesList from './components/JournalEntriesList';
 import AdminEntityForm from './components/AdminEntityForm';
 import DatePickerField from './components/DatePickerField';
 import ThemeToggle from './components/ThemeToggle';
+import { errorMessage } from './utils/errors';
 import "./App.css";
 
 type AdminEntityType = 'project' | 'tag' | 'activityType';
 type AdminMode = 'create' | 'edit';
 
+type TicketProvider = 'jira' | 'demo' | 'fixture';
+
+interface JiraSearchPage {
+  tickets: any[];
+  next_page_token: string | null;
+  has_more: boolean;
+  provider: TicketProvider;
+}
+
 interface AdminViewState {
   view: 'list' | 'form';
   entityType: AdminEntityType | null;
@@ -25,6 +35,14 @@ export default function App() {
   const [currentContent, setCurrentContent] = useState('');
   const [jiraTickets, setJiraTickets] = useState<any[]>([]);
   const [jiraQuery, setJiraQuery] = useState('');
+  const [jiraNextPageToken, setJiraNextPageToken] = useState<string | null>(null);
+  const [ticketProvider, setTicketProvider] = useState<TicketProvider>('jira');
+  const [ticketFixturePath, setTicketFixturePath] = useState('');
+  const [answeringProvider, setAnsweringProvider] = useState<TicketProvider | null>(null);
+  const [activityStartDate, setActivityStartDate] = useState(new Date().toISOString().split('T')[0]);
+  const [activityEndDate, setActivityEndDate] = useState(new Date().toISOString().split('T')[0]);
+  const [draftEntries, setDraftEntries] = useState<any[]>([]);
+  const [isLoadingDrafts, setIsLoadingDrafts] = useState(false);
   const [isLoadingJira, setIsLoadingJira] = useState(false);
   
   // États pour l'administration
@@ -61,6 +79,7 @@ export default function App() {
 
   useEffect(() => {
     loadJournalDates();
+    loadTicketProvider();
     loadSavedJqlQuery();
     loadFormReferenceData();
   }, []);
@@ -165,6 +184,35 @@ export default function App() {
     }
   };
 
+  const loadTicketProvider = async () => {
+    try {
+      const provider = await invoke<string | null>('get_preference', { key: 'ticket_provider' });
+      const fixturePath = await invoke<string | null>('get_preference', { key: 'ticket_fixture_path' });
+      setTicketProvider((provider as TicketProvider) || 'jira');
+      setTicketFixturePath(fixturePath || '');
+    } catch (error) {
+      console.error('Erreur lors du chargement de la source des tickets:', error);
+    }
+  };
+
+  const handleTicketProviderChange = async (provider: TicketProvider) => {
+    setTicketProvider(provider);
+    try {
+      await invoke('set_preference', { key: 'ticket_provider', value: provider });
+    } catch (error) {
+      alert(`Erreur: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleTicketFixturePathChange = async (path: string) => {
+    setTicketFixturePath(path);
+    try {
+      await invoke('set_preference', { key: 'ticket_fixture_path', value: path });
+    } catch (error) {
+      console.error('Erreur lors de la sauvegarde du fichier de tickets:', error);
+    }
+  };
+
   const loadSavedJqlQuery = async () => {
     try {
       const savedQuery = await invoke<string | null>('get_preference', { key: 'jira_jql_query' });
@@ -172,8 +220,10 @@ export default function App() {
         setJiraQuery(savedQuery);
         setIsLoadingJira(true);
         try {
-          const tickets = await invoke<any[]>('fetch_jira_tickets', { query: savedQuery });
-          setJiraTickets(tickets);
+          const page = await invoke<JiraSearchPage>('fetch_jira_tickets', { query: savedQuery });
+          setJiraTickets(page.tickets);
+          setJiraNextPageToken(page.has_more ? page.next_page_token : null);
+          setAnsweringProvider(page.provider);
         } catch (error) {
           console.error("Erreur lors du chargement automatique des tickets Jira :", error);
         } finally {
@@ -195,6 +245,12 @@ export default function App() {
     }
   };
 
+  // Un renommage réécrit les entrées du journal : recharger le jour affiché
+  const refreshRewrittenJournal = async () => {
+    await loadJournal(currentDate);
+    setEntriesRefreshKey(prev => prev + 1);
+  };
+
   const handleSubmit = async (entry: any) => {
     const targetDate = entry.date || currentDate;
 
@@ -214,18 +270,73 @@ export default function App() {
   const fetchJiraTickets = async () => {
     try {
       setIsLoadingJira(true);
-      const tickets = await invoke<any[]>('fetch_jira_tickets', { query: jiraQuery });
-      setJiraTickets(tickets);
+      const page = await invoke<JiraSearchPage>('fetch_jira_tickets', { query: jiraQuery });
+      setJiraTickets(page.tickets);
+      setJiraNextPageToken(page.has_more ? page.next_page_token : null);
+      setAnsweringProvider(page.provider);
       await invoke('set_preference', { key: 'jira_jql_query', value: jiraQuery });
     } catch (error) {
       console.error("Erreur Jira :", error);
-      alert(`Erreur Jira: ${error}`);
+      alert(`Erreur Jira: ${errorMessage(error)}`);
+    } finally {
+      setIsLoadingJira(false);
+    }
+  };
+
+  const fetchMoreJiraTickets = async () => {
+    if (!jiraNextPageToken) return;
+    try {
+      setIsLoadingJira(true);
+      const page = await invoke<JiraSearchPage>('fetch_jira_tickets', {
+        query: jiraQuery,
+        pageToken: jiraNextPageToken,
+      });
+      setJiraTickets(prev => [...prev, ...page.tickets]);
+      setJiraNextPageToken(page.has_more ? page.next_page_token : null);
+      setAnsweringProvider(page.provider);
+    } catch (error) {
+      console.error("Erreur Jira :", error);
+      alert(`Erreur Jira: ${errorMessage(error)}`);
     } finally {
       setIsLoadingJira(false);
     }
   };
 
 
+  const fetchDraftEntries = async () => {
+    try {
+      setIsLoadingDrafts(true);
+      const drafts = await invoke<any[]>('draft_entries_from_jira', {
+        startDate: activityStartDate,
+        endDate: activityEndDate,
+      });
+      setDraftEntries(drafts);
+    } catch (error) {
+      console.error("Erreur Jira :", error);
+      alert(`Erreur Jira: ${errorMessage(error)}`);
+    } finally {
+      setIsLoadingDrafts(false);
+    }
+  };
+
+  const updateDraftDuration = (index: number, duration: string) => {
+    setDraftEntries(prev => prev.map((draft, i) =>
+      i === index ? { ...draft, entry: { ...draft.entry, duration } } : draft
+    ));
+  };
+
+  const acceptDraftEntry = async (index: number) => {
+    const draft = draftEntries[index];
+    try {
+      await invoke('save_journal_entry_cmd', { date: draft.entry.date, entry: draft.entry });
+      setDraftEntries(prev => prev.filter((_, i) => i !== index));
+      await loadJournalDates();
+      setEntriesRefreshKey(prev => prev + 1);
+    } catch (error) {
+      alert(`Erreur lors de l'ajout: ${errorMessage(error)}`);
+    }
+  };
+
   const handleJiraQueryChange = async (newQuery: string) => {
     setJiraQuery(newQuery);
     try {
@@ -275,15 +386,38 @@ export default function App() {
     }
   };
 
+  // Un renommage réécrit les entrées du journal : il est simulé puis confirmé
+  // avant d'être appliqué. Renvoie false si l'utilisateur renonce.
+  const updateCatalogItem = async (
+    command: 'update_project' | 'update_tag',
+    args: Record<string, unknown>
+  ): Promise<boolean> => {
+    const preview = await invoke<{ entries: number }>(command, { ...args, dryRun: true });
+    if (
+      preview.entries > 0 &&
+      !confirm(`Ce renommage modifiera ${preview.entries} entrée(s) du journal. Continuer ?`)
+    ) {
+      return false;
+    }
+    const rewrite = await invoke<{ entries: number }>(command, { ...args, dryRun: false });
+    if (rewrite.entries > 0) {
+      await refreshRewrittenJournal();
+    }
+    return true;
+  };
+
   const handleSaveProject = async (projectData: any) => {
     try {
       if (adminView.entityType === 'project' && adminView.mode === 'edit' && adminView.initialData) {
-        await invoke('update_project', {
+        const applied = await updateCatalogItem('update_project', {
           id: adminView.initialData.id,
           name: projectData.name,
           description: projectData.description,
           color: projectData.color
         });
+        if (!applied) {
+          return;
+        }
       } else {
         await invoke('create_project', {
           name: projectData.name,
@@ -295,7 +429,7 @@ export default function App() {
       closeAdminForm(true);
     } catch (error) {
       console.error('Erreur lors de la sauvegarde du projet:', error);
-      alert('Erreur lors de la sauvegarde du projet');
+      alert(`Erreur lors de la sauvegarde du projet: ${errorMessage(error)}`);
     }
   };
 
@@ -323,12 +457,15 @@ export default function App() {
   const handleSaveTag = async (tagData: any) => {
     try {
       if (adminView.entityType === 'tag' && adminView.mode === 'edit' && adminView.initialData) {
-        await invoke('update_tag', {
+        const applied = await updateCatalogItem('update_tag', {
           id: adminView.initialData.id,
           name: tagData.name,
           description: tagData.description,
           color: tagData.color
         });
+        if (!applied) {
+          return;
+        }
       } else {
         await invoke('create_tag', {
           name: tagData.name,
@@ -340,7 +477,7 @@ export default function App() {
       closeAdminForm(true);
     } catch (error) {
       console.error('Erreur lors de la sauvegarde du tag:', error);
-      alert('Erreur lors de la sauvegarde du tag');
+      alert(`Erreur lors de la sauvegarde du tag: ${errorMessage(error)}`);
     }
   };
 
@@ -480,7 +617,7 @@ export default function App() {
       }
     } catch (error) {
       console.error('Erreur lors de l\'export DOCX:', error);
-      alert(`Erreur lors de l'export: ${error}`);
+      alert(`Erreur lors de l'export: ${errorMessage(error)}`);
     }
   };
 
@@ -557,6 +694,27 @@ export default function App() {
         <div className="tab-content">
           <h2>Tickets Jira</h2>
           <div className="jira-search">
+            <label>Source des tickets:</label>
+            <div className="jira-input-group">
+              <select
+                value={ticketProvider}
+                onChange={(e) => handleTicketProviderChange(e.target.value as TicketProvider)}
+                disabled={isLoadingJira}
+              >
+                <option value="jira">Jira</option>
+                <option value="demo">Démonstration</option>
+                <option value="fixture">Fichier de tickets (JSON)</option>
+              </select>
+              {ticketProvider === 'fixture' && (
+                <input
+                  type="text"
+                  value={ticketFixturePath}
+                  onChange={(e) => handleTicketFixturePathChange(e.target.value)}
+                  placeholder="/chemin/vers/tickets.json"
+                  disabled={isLoadingJira}
+                />
+              )}
+            </div>
             <label>Requête JQL:</label>
             <div className="jira-input-group">
               <input
@@ -575,6 +733,14 @@ export default function App() {
 
           <div>
             {jiraTickets.length > 0 ? (
+              <>
+              {answeringProvider && answeringProvider !== 'jira' && (
+                <p className="provider-notice">
+                  {answeringProvider === 'demo'
+                    ? 'Tickets de démonstration : ces données ne viennent pas de Jira.'
+                    : `Tickets lus dans le fichier ${ticketFixturePath} : ces données ne viennent pas de Jira.`}
+                </p>
+              )}
               <table>
                 <thead>
                   <tr>
@@ -595,10 +761,80 @@ export default function App() {
                   ))}
                 </tbody>
               </table>
+              {jiraNextPageToken && (
+                <button onClick={fetchMoreJiraTickets} disabled={isLoadingJira} className="jira-button">
+                  {isLoadingJira ? 'Chargement...' : 'Charger plus de tickets'}
+                </button>
+              )}
+              </>
             ) : (
               <p>Aucun ticket trouvé. Vérifie ta requête JQL ou configure Jira dans ton fichier .env.</p>
             )}
           </div>
+
+          <div className="jira-activity">
+            <h3>Mon activité Jira</h3>
+            <div className="jira-input-group">
+              <DatePickerField
+                label="Du"
+                value={activityStartDate}
+                onChange={(nextValue) => setActivityStartDate(nextValue)}
+              />
+              <DatePickerField
+                label="Au"
+                value={activityEndDate}
+                onChange={(nextValue) => setActivityEndDate(nextValue)}
+              />
+              <button onClick={fetchDraftEntries} disabled={isLoadingDrafts} className="jira-button">
+                {isLoadingDrafts ? 'Chargement...' : 'Proposer des entrées'}
+              </button>
+            </div>
+            {draftEntries.length > 0 && (
+              <table>
+                <thead>
+                  <tr>
+                    <th>Date</th>
+                    <th>Ticket</th>
+                    <th>Projet</th>
+                    <th>Description</th>
+                    <th>Durée</th>
+                    <th></th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {draftEntries.map((draft, index) => (
+                    <tr key={`${draft.entry.date}-${draft.entry.jira_tickets[0]?.key}`}>
+                      <td>{draft.entry.date}</td>
+                      <td>{draft.entry.jira_tickets[0]?.key}</td>
+                      <td>{draft.entry.project}</td>
+                      <td style={{ whiteSpace: 'pre-line' }}>
+                        {draft.entry.description}
+                        {draft.entry.results && `\n${draft.entry.results}`}
+                      </td>
+                      <td>
+                        <input
+                          type="text"
+                          value={draft.entry.duration}
+                          onChange={(e) => updateDraftDuration(index, e.target.value)}
+                          placeholder="ex: 1h30"
+                        />
+                      </td>
+                      <td>
+                        <button
+                          onClick={() => acceptDraftEntry(index)}
+                          className="jira-button"
+                          disabled={draft.needs_duration && !draft.entry.duration.trim()}
+                          title={draft.needs_duration && !draft.entry.duration.trim() ? 'Saisissez une durée' : undefined}
+                        >
+                          Ajouter au journal
+                        </button>
+                      </td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            )}
+          </div>
         </div>
       ) : activeTab === 'admin' ? (
         <div className="tab-content">
@@ -958,6 +1194,20 @@ export default function App() {
                 </div>
               </div>
 
+              {/* Durées non comptées */}
+              {activityReport.duration_issues?.length > 0 && (
+                <div className="report-section">
+                  <h4>Durées non comptées ({activityReport.duration_issues.length})</h4>
+                  <ul>
+                    {activityReport.duration_issues.map((issue: any) => (
+                      <li key={`${issue.date}-${issue.entry_id}`}>
+                        {issue.date} {issue.timestamp} : « {issue.value} » — {issue.reason}
+                      </li>
+                    ))}
+                  </ul>
+                </div>
+              )}
+
               {/* Projets */}
               {activityReport.projects_summary.length > 0 && (
                 <div className="report-section">
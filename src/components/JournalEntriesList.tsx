@@ -1,10 +1,12 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { calculateDurationFromTimeRange, formatTimeRangeInput } from '../utils/timeRange';
+import { errorMessage } from '../utils/errors';
 
 interface JiraTicketRef {
   key: string;
   summary?: string;
+  url?: string;
 }
 
 interface JiraTicketForEntry {
@@ -21,6 +23,7 @@ interface Link {
 }
 
 interface ParsedJournalEntry {
+  id: string;
   timestamp: string;
   project: string;
   description: string;
@@ -118,23 +121,19 @@ export default function JournalEntriesList({
     if (editForm && editingIndex !== null) {
       try {
         setLoading(true);
-        const success = await invoke<boolean>('update_journal_entry_cmd', {
+        await invoke('update_journal_entry_cmd', {
           date,
-          entryIndex: editingIndex,
+          entryId: editForm.id,
           updatedEntry: editForm
         });
-        
-        if (success) {
-          await loadEntries();
-          setEditingIndex(null);
-          setEditForm(null);
-          onRefresh();
-        } else {
-          alert('Erreur lors de la mise à jour de l\'entrée');
-        }
+
+        await loadEntries();
+        setEditingIndex(null);
+        setEditForm(null);
+        onRefresh();
       } catch (error) {
         console.error('Erreur lors de la sauvegarde:', error);
-        alert('Erreur lors de la sauvegarde');
+        alert(`Erreur lors de la sauvegarde : ${errorMessage(error)}`);
       } finally {
         setLoading(false);
       }
@@ -145,20 +144,16 @@ export default function JournalEntriesList({
     if (confirm('Êtes-vous sûr de vouloir supprimer cette entrée ?')) {
       try {
         setLoading(true);
-        const success = await invoke<boolean>('delete_journal_entry_cmd', {
+        await invoke('delete_journal_entry_cmd', {
           date,
-          entryIndex: index
+          entryId: entries[index].id
         });
-        
-        if (success) {
-          await loadEntries();
-          onRefresh();
-        } else {
-          alert('Erreur lors de la suppression de l\'entrée');
-        }
+
+        await loadEntries();
+        onRefresh();
       } catch (error) {
         console.error('Erreur lors de la suppression:', error);
-        alert('Erreur lors de la suppression');
+        alert(`Erreur lors de la suppression : ${errorMessage(error)}`);
       } finally {
         setLoading(false);
       }
@@ -246,7 +241,7 @@ export default function JournalEntriesList({
       ) : (
         <div className="entries-list">
           {entries.map((entry, index) => (
-            <div key={index} className="entry-card">
+            <div key={entry.id} className="entry-card">
               {editingIndex === index ? (
                 // Formulaire d'édition
                 <div className="edit-form">
@@ -11,6 +11,7 @@ interface Link {
 interface JiraTicketRef {
   key: string;
   summary?: string;
+  url?: string;
 }
 
 interface JiraTicketForEntry {
@@ -262,7 +263,7 @@ export default function JournalEntryForm({
 
       <div>
         <label>Durée:</label>
-        <input type="text" name="duration" value={entry.duration} onChange={handleChange} placeholder="3h" required />
+        <input type="text" name="duration" value={entry.duration} onChange={handleChange} placeholder="3h (ou déduite de la plage horaire)" />
       </div>
 
       <div>
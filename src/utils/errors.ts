@@ -0,0 +1,38 @@
+// Erreur renvoyée par les commandes Tauri (voir src-tauri/src/error.rs)
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+export interface AppError {
+  code:
+    | 'NotFound'
+    | 'Validation'
+    | 'Conflict'
+    | 'Io'
+    | 'JiraNotConfigured'
+    | 'JiraAuth'
+    | 'JiraRateLimited'
+    | 'JiraUnavailable'
+    | 'Jira'
+    | 'Internal';
+  message: string;
+  details: FieldError[];
+  retry_after: number | null;
+}
+
+export function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    'code' in error &&
+    'message' in error
+  );
+}
+
+export function errorMessage(error: unknown): string {
+  if (isAppError(error)) {
+    return error.message;
+  }
+  return String(error);
+}